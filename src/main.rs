@@ -1,16 +1,26 @@
 use crate::logger::Logger;
+use crate::managers::analysis::AnalysisManager;
 use crate::managers::audio_device_settings::AudioDeviceSettingsManager;
 use crate::managers::cache::CacheManager;
+use crate::managers::chain::ChainManager;
 use crate::managers::database::DatabaseManager;
-use crate::managers::dsp::DSPManager;
+use crate::managers::dsp::audio_config::load_audio_config;
+use crate::managers::dsp::{stop_gracefully, DSPManager};
+use crate::managers::events::EventsManager;
 use crate::managers::file_system::FileSystemManager;
+use crate::managers::history::HistoryManager;
+use crate::managers::midi::MidiManager;
+use crate::managers::osc::OscManager;
 use crate::managers::performance::PerformanceManager;
+use crate::managers::presets::PresetsManager;
+use crate::managers::session::SessionManager;
 use crate::routes::create_router;
 use crate::server::Server;
 use crate::settings::Settings;
 use axum::Extension;
 use clap::Parser;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tower_http::trace::TraceLayer;
 
 mod logger;
@@ -26,26 +36,132 @@ async fn main() {
 
     Logger::new(&settings).init();
 
-    let dsp_manager = Arc::new(Mutex::new(DSPManager::new(&settings).unwrap()));
-    let performance_manager = Arc::new(PerformanceManager::new());
     let file_system_manager = FileSystemManager::new().unwrap();
-    let database_manager = DatabaseManager::new(&settings).await.unwrap();
+    let mut dsp_manager = match load_audio_config(&file_system_manager) {
+        Some(audio_config) => DSPManager::new(&audio_config).unwrap_or_else(|error| {
+            tracing::error!(
+                "cannot use the saved audio config, falling back to the command line: {:?}",
+                error
+            );
+            DSPManager::new(&settings.audio_config()).unwrap()
+        }),
+        None => DSPManager::new(&settings.audio_config()).unwrap(),
+    };
+    let analysis_manager = AnalysisManager::new(dsp_manager.take_analysis_source().unwrap());
+    let dsp_manager = Arc::new(Mutex::new(dsp_manager));
+    let performance_manager = Arc::new(PerformanceManager::new());
+    let database_manager = match DatabaseManager::new(&settings, &file_system_manager).await {
+        Ok(database_manager) => database_manager,
+        Err(error) => {
+            tracing::error!(
+                "cannot initialise {:?} storage: {:?}",
+                settings.storage,
+                error
+            );
+            std::process::exit(1);
+        }
+    };
     let cache_manager = Arc::new(Mutex::new(CacheManager::new()));
+    let events_manager = EventsManager::new();
+    let history_manager = HistoryManager::new(
+        dsp_manager.clone(),
+        events_manager.clone(),
+        settings.history_size,
+    );
+    let chain_manager = ChainManager::new(
+        dsp_manager.clone(),
+        events_manager.clone(),
+        history_manager.clone(),
+    );
+    let presets_manager = PresetsManager::new(
+        cache_manager.clone(),
+        database_manager.clone(),
+        dsp_manager.clone(),
+        events_manager.clone(),
+        history_manager.clone(),
+        settings.max_preset_revisions as usize,
+    );
+    let audio_device_settings_manager = if settings.hifiberry_enabled {
+        Some(Arc::new(AudioDeviceSettingsManager::new().unwrap()))
+    } else {
+        None
+    };
+    let midi_manager = MidiManager::new(
+        audio_device_settings_manager.clone(),
+        chain_manager.clone(),
+        database_manager.clone(),
+        dsp_manager.clone(),
+        events_manager.clone(),
+        presets_manager.clone(),
+    )
+    .await;
+    let session_manager = SessionManager::new(
+        audio_device_settings_manager.clone(),
+        cache_manager.clone(),
+        dsp_manager.clone(),
+        events_manager.clone(),
+        file_system_manager.clone(),
+    );
+
+    let is_session_restored = settings.resume_session && session_manager.restore();
+    if !is_session_restored {
+        if let Some(default_preset_id) = database_manager.get_default_preset_id().await {
+            presets_manager.load_preset(default_preset_id).await;
+        }
+    }
+    if settings.resume_session {
+        session_manager.start_autosave(Duration::from_secs(settings.session_save_interval_seconds));
+    }
 
     dsp_manager.lock().unwrap().start().unwrap();
+    if settings.midi_enabled {
+        if let Err(error) = midi_manager.start() {
+            tracing::error!("cannot start MIDI input: {:?}", error);
+        }
+    }
+    if settings.osc_enabled {
+        match OscManager::new(
+            settings.osc_connection_string(),
+            audio_device_settings_manager.clone(),
+            chain_manager.clone(),
+            dsp_manager.clone(),
+            events_manager.clone(),
+            presets_manager.clone(),
+        )
+        .await
+        {
+            Ok(osc_manager) => osc_manager.start(),
+            Err(error) => tracing::error!("cannot start the OSC server: {:?}", error),
+        }
+    }
 
     let mut app = create_router()
         .layer(TraceLayer::new_for_http())
-        .layer(Extension(dsp_manager))
+        .layer(Extension(dsp_manager.clone()))
         .layer(Extension(cache_manager))
+        .layer(Extension(chain_manager))
         .layer(Extension(database_manager))
+        .layer(Extension(events_manager))
+        .layer(Extension(history_manager))
+        .layer(Extension(analysis_manager))
+        .layer(Extension(presets_manager))
+        .layer(Extension(midi_manager))
         .layer(Extension(file_system_manager))
         .layer(Extension(performance_manager));
 
-    if settings.hifiberry_enabled {
-        let audio_device_settings_manager = Arc::new(AudioDeviceSettingsManager::new().unwrap());
+    if let Some(audio_device_settings_manager) = audio_device_settings_manager {
         app = app.layer(Extension(audio_device_settings_manager));
     }
 
-    Server::new(&settings).start(&app).await.unwrap();
+    if let Err(error) = Server::new(&settings).start(&app).await {
+        tracing::error!("server error: {:?}", error);
+    }
+
+    if let Err(error) = stop_gracefully(&dsp_manager).await {
+        tracing::error!("cannot stop the audio streams: {:?}", error);
+    }
+    if settings.resume_session {
+        session_manager.save();
+    }
+    tracing::info!("shutdown complete");
 }
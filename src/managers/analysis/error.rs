@@ -0,0 +1,7 @@
+#[derive(Debug)]
+pub enum Error {
+    FftSize,
+    Averaging,
+    ScopeLength,
+    ScopePoints,
+}
@@ -0,0 +1,268 @@
+use crate::managers::analysis::error::Error;
+use crate::managers::analysis::models::{AnalysisSettings, Scope, Spectrum, TapPoint};
+use crate::managers::dsp::analysis_tap::{
+    get_processor_stage, AnalysisSource, INPUT_STAGE, OUTPUT_STAGE,
+};
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use ringbuf::traits::Consumer;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub mod error;
+pub mod models;
+
+static MIN_FFT_SIZE: usize = 64;
+static MAX_FFT_SIZE: usize = 16384;
+static MAX_SCOPE_LENGTH_MS: f32 = 1000.0;
+static MAX_SCOPE_POINTS: usize = 4096;
+static ANALYSIS_PERIOD: Duration = Duration::from_millis(16);
+// Below this the bins are shown as silence instead of minus infinity
+static MIN_MAGNITUDE_DB: f32 = -140.0;
+
+struct AnalysisState {
+    settings: AnalysisSettings,
+    spectrum: Spectrum,
+    scope: Scope,
+}
+
+/// Computes the spectrum and the waveform of the tapped audio in a background thread.
+#[derive(Clone)]
+pub struct AnalysisManager {
+    state: Arc<Mutex<AnalysisState>>,
+    source_tap_stage: Arc<AtomicUsize>,
+}
+
+impl AnalysisManager {
+    pub fn new(source: AnalysisSource) -> Self {
+        let settings = AnalysisSettings::default();
+        source
+            .tap_stage
+            .store(get_tap_stage(&settings.tap), Ordering::Relaxed);
+
+        let state = Arc::new(Mutex::new(AnalysisState {
+            settings,
+            spectrum: Spectrum::default(),
+            scope: Scope::default(),
+        }));
+        let manager = Self {
+            state: state.clone(),
+            source_tap_stage: source.tap_stage.clone(),
+        };
+
+        let mut analyser = Analyser::new(source, state);
+        thread::spawn(move || loop {
+            thread::sleep(ANALYSIS_PERIOD);
+            analyser.run();
+        });
+
+        manager
+    }
+
+    pub fn get_settings(&self) -> AnalysisSettings {
+        self.state.lock().unwrap().settings.clone()
+    }
+
+    pub fn set_settings(&self, settings: AnalysisSettings) -> Result<(), Error> {
+        if !settings.fft_size.is_power_of_two()
+            || !(MIN_FFT_SIZE..=MAX_FFT_SIZE).contains(&settings.fft_size)
+        {
+            return Err(Error::FftSize);
+        }
+        if !(0.0..1.0).contains(&settings.averaging) {
+            return Err(Error::Averaging);
+        }
+        if !(settings.scope_length_ms > 0.0 && settings.scope_length_ms <= MAX_SCOPE_LENGTH_MS) {
+            return Err(Error::ScopeLength);
+        }
+        if !(1..=MAX_SCOPE_POINTS).contains(&settings.scope_points) {
+            return Err(Error::ScopePoints);
+        }
+
+        self.source_tap_stage
+            .store(get_tap_stage(&settings.tap), Ordering::Relaxed);
+        self.state.lock().unwrap().settings = settings;
+        Ok(())
+    }
+
+    pub fn get_spectrum(&self) -> Spectrum {
+        self.state.lock().unwrap().spectrum.clone()
+    }
+
+    pub fn get_scope(&self) -> Scope {
+        self.state.lock().unwrap().scope.clone()
+    }
+}
+
+fn get_tap_stage(tap: &TapPoint) -> usize {
+    match tap {
+        TapPoint::Input => INPUT_STAGE,
+        TapPoint::Processor { index } => get_processor_stage(*index),
+        TapPoint::Output => OUTPUT_STAGE,
+    }
+}
+
+fn get_history_size(sample_rate: u32) -> usize {
+    MAX_FFT_SIZE.max((MAX_SCOPE_LENGTH_MS / 1000.0 * sample_rate as f32) as usize)
+}
+
+struct Analyser {
+    source: AnalysisSource,
+    state: Arc<Mutex<AnalysisState>>,
+    sample_rate: u32,
+    settings: AnalysisSettings,
+    history: Vec<f32>,
+    history_index: usize,
+    new_samples_count: usize,
+    planner: RealFftPlanner<f32>,
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    window_gain: f32,
+    frame: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    averaged_magnitudes: Vec<f32>,
+}
+
+impl Analyser {
+    fn new(source: AnalysisSource, state: Arc<Mutex<AnalysisState>>) -> Self {
+        let settings = state.lock().unwrap().settings.clone();
+        let sample_rate = source.sample_rate.load(Ordering::Relaxed);
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(settings.fft_size);
+
+        let mut analyser = Self {
+            source,
+            state,
+            sample_rate,
+            settings: settings.clone(),
+            history: vec![0.0; get_history_size(sample_rate)],
+            history_index: 0,
+            new_samples_count: 0,
+            planner,
+            fft,
+            window: vec![],
+            window_gain: 1.0,
+            frame: vec![],
+            spectrum: vec![],
+            scratch: vec![],
+            averaged_magnitudes: vec![],
+        };
+        analyser.configure(settings);
+        analyser
+    }
+
+    fn configure(&mut self, settings: AnalysisSettings) {
+        let fft_size = settings.fft_size;
+        self.fft = self.planner.plan_fft_forward(fft_size);
+        self.window = settings.window.get_coefficients(fft_size);
+        self.window_gain = self.window.iter().sum::<f32>();
+        self.frame = self.fft.make_input_vec();
+        self.spectrum = self.fft.make_output_vec();
+        self.scratch = self.fft.make_scratch_vec();
+        self.averaged_magnitudes = vec![0.0; fft_size / 2 + 1];
+        self.settings = settings;
+    }
+
+    fn run(&mut self) {
+        let sample_rate = self.source.sample_rate.load(Ordering::Relaxed);
+        if sample_rate != self.sample_rate {
+            // Samples recorded at the previous rate would distort both views
+            self.sample_rate = sample_rate;
+            self.history = vec![0.0; get_history_size(sample_rate)];
+            self.history_index = 0;
+            self.new_samples_count = 0;
+        }
+        self.read_source();
+
+        let settings = self.state.lock().unwrap().settings.clone();
+        if settings.fft_size != self.settings.fft_size || settings.window != self.settings.window {
+            self.configure(settings);
+        } else {
+            self.settings = settings;
+        }
+
+        // Frames overlap by half, so the spectrum is refreshed often even with big sizes
+        let spectrum = if self.new_samples_count >= self.settings.fft_size / 2 {
+            self.new_samples_count = 0;
+            Some(self.compute_spectrum())
+        } else {
+            None
+        };
+        let scope = self.compute_scope();
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(spectrum) = spectrum {
+            state.spectrum = spectrum;
+        }
+        state.scope = scope;
+    }
+
+    fn read_source(&mut self) {
+        loop {
+            let (_, tail) = self.history.split_at_mut(self.history_index);
+            let read_count = self.source.consumer.pop_slice(tail);
+            if read_count == 0 {
+                break;
+            }
+            self.history_index = (self.history_index + read_count) % self.history.len();
+            self.new_samples_count += read_count;
+        }
+    }
+
+    fn copy_latest_samples(&self, destination: &mut [f32]) {
+        let size = self.history.len();
+        let start = (self.history_index + size - destination.len()) % size;
+        for (index, sample) in destination.iter_mut().enumerate() {
+            *sample = self.history[(start + index) % size];
+        }
+    }
+
+    fn compute_spectrum(&mut self) -> Spectrum {
+        let mut frame = std::mem::take(&mut self.frame);
+        self.copy_latest_samples(&mut frame);
+        for (sample, coefficient) in frame.iter_mut().zip(self.window.iter()) {
+            *sample *= coefficient;
+        }
+        // The sizes come from the plan itself, so this cannot fail
+        let _ = self
+            .fft
+            .process_with_scratch(&mut frame, &mut self.spectrum, &mut self.scratch);
+        self.frame = frame;
+
+        let averaging = self.settings.averaging;
+        // A full scale sine reads 0 dBFS whatever the window
+        let normalisation = 2.0 / self.window_gain;
+        let magnitudes = self
+            .averaged_magnitudes
+            .iter_mut()
+            .zip(self.spectrum.iter())
+            .map(|(averaged, bin)| {
+                *averaged = averaging * *averaged + (1.0 - averaging) * bin.norm() * normalisation;
+                (20.0 * averaged.log10()).max(MIN_MAGNITUDE_DB)
+            })
+            .collect();
+
+        Spectrum {
+            sample_rate: self.sample_rate,
+            fft_size: self.settings.fft_size,
+            magnitudes,
+        }
+    }
+
+    fn compute_scope(&self) -> Scope {
+        let length = ((self.settings.scope_length_ms / 1000.0 * self.sample_rate as f32) as usize)
+            .clamp(1, self.history.len());
+        let decimation = (length / self.settings.scope_points).max(1);
+        let mut samples = vec![0.0; length];
+        self.copy_latest_samples(&mut samples);
+
+        Scope {
+            sample_rate: self.sample_rate,
+            decimation,
+            samples: samples.into_iter().step_by(decimation).collect(),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TapPoint {
+    Input,
+    Processor { index: usize },
+    Output,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    Blackman,
+}
+
+impl WindowFunction {
+    pub fn get_coefficients(&self, size: usize) -> Vec<f32> {
+        let step = 2.0 * std::f32::consts::PI / size as f32;
+        (0..size)
+            .map(|index| {
+                let phase = step * index as f32;
+                match self {
+                    Self::Rectangular => 1.0,
+                    Self::Hann => 0.5 - 0.5 * phase.cos(),
+                    Self::Blackman => 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnalysisSettings {
+    pub tap: TapPoint,
+    pub fft_size: usize,
+    pub window: WindowFunction,
+    /// Exponential averaging factor of the spectrum, 0 disables it
+    pub averaging: f32,
+    pub scope_length_ms: f32,
+    pub scope_points: usize,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        Self {
+            tap: TapPoint::Output,
+            fft_size: 4096,
+            window: WindowFunction::Hann,
+            averaging: 0.7,
+            scope_length_ms: 50.0,
+            scope_points: 512,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct Spectrum {
+    pub sample_rate: u32,
+    pub fft_size: usize,
+    /// Magnitude of each bin from DC to Nyquist, in dBFS
+    pub magnitudes: Vec<f32>,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct Scope {
+    pub sample_rate: u32,
+    /// How many input samples each point of the waveform spans
+    pub decimation: usize,
+    pub samples: Vec<f32>,
+}
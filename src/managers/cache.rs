@@ -1,14 +1,19 @@
+use crate::managers::database::models::Scene;
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct CacheManager {
     pub current_preset_id: Option<Uuid>,
+    pub current_scenes: Vec<Scene>,
+    pub current_scene_index: Option<usize>,
 }
 
 impl CacheManager {
     pub fn new() -> Self {
         Self {
             current_preset_id: None,
+            current_scenes: vec![],
+            current_scene_index: None,
         }
     }
 }
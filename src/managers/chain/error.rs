@@ -0,0 +1,15 @@
+use crate::managers::dsp::error::TransformProcessorError;
+
+#[derive(Debug)]
+pub enum Error {
+    ProcessorNotFound,
+    UnsupportedParameter,
+}
+
+impl From<TransformProcessorError> for Error {
+    fn from(value: TransformProcessorError) -> Self {
+        match value {
+            TransformProcessorError::NotFound => Error::ProcessorNotFound,
+        }
+    }
+}
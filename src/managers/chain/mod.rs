@@ -0,0 +1,169 @@
+use crate::managers::chain::error::Error;
+use crate::managers::dsp::models::{ProcessorInfo, ProcessorType};
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::history::models::ChainEdit;
+use crate::managers::history::HistoryManager;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use std::sync::{Arc, Mutex};
+
+pub mod error;
+
+/// Edits the live chain on behalf of every control surface (REST, WebSocket, OSC and MIDI),
+/// so that each edit is recorded in the history and published the same way.
+#[derive(Clone)]
+pub struct ChainManager {
+    dsp_manager: Arc<Mutex<DSPManager>>,
+    events_manager: EventsManager,
+    history_manager: HistoryManager,
+}
+
+impl ChainManager {
+    pub fn new(
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        history_manager: HistoryManager,
+    ) -> Self {
+        Self {
+            dsp_manager,
+            events_manager,
+            history_manager,
+        }
+    }
+
+    pub fn create_processor(
+        &self,
+        index: usize,
+        processor_type: &ProcessorType,
+    ) -> Result<ProcessorInfo, Error> {
+        let processor = self
+            .dsp_manager
+            .lock()
+            .unwrap()
+            .create_processor(index, processor_type)?;
+        self.history_manager.record(ChainEdit::Create {
+            index,
+            processor: processor.clone(),
+        });
+        self.events_manager.publish(StateEvent::ProcessorCreated {
+            index,
+            processor: processor.clone(),
+        });
+        Ok(processor)
+    }
+
+    pub fn swap_processor(
+        &self,
+        index: usize,
+        processor_type: &ProcessorType,
+    ) -> Result<ProcessorInfo, Error> {
+        let (previous_processor, processor) = {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            let previous_processor = dsp_manager
+                .get_processor_info(index)
+                .ok_or(Error::ProcessorNotFound)?;
+            let processor = dsp_manager.swap_processor(index, processor_type)?;
+            (previous_processor, processor)
+        };
+        self.history_manager.record(ChainEdit::Swap {
+            index,
+            from: previous_processor,
+            to: processor.clone(),
+        });
+        self.events_manager.publish(StateEvent::ProcessorSwapped {
+            index,
+            processor: processor.clone(),
+        });
+        Ok(processor)
+    }
+
+    pub fn delete_processor(&self, index: usize) -> Result<(), Error> {
+        let processor = {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            let processor = dsp_manager
+                .get_processor_info(index)
+                .ok_or(Error::ProcessorNotFound)?;
+            dsp_manager.delete_processor(index)?;
+            processor
+        };
+        self.history_manager
+            .record(ChainEdit::Delete { index, processor });
+        self.events_manager
+            .publish(StateEvent::ProcessorDeleted { index });
+        Ok(())
+    }
+
+    pub fn move_processor(&self, index: usize, destination_index: usize) -> Result<(), Error> {
+        self.dsp_manager
+            .lock()
+            .unwrap()
+            .move_processor(index, destination_index)?;
+        self.history_manager.record(ChainEdit::Move {
+            index,
+            destination_index,
+        });
+        self.events_manager.publish(StateEvent::ProcessorMoved {
+            index,
+            destination_index,
+        });
+        Ok(())
+    }
+
+    pub fn set_bypassed(&self, index: usize, bypassed: bool) -> Result<(), Error> {
+        let mut was_bypassed = bypassed;
+        self.dsp_manager
+            .lock()
+            .unwrap()
+            .transform_processor_settings(index, |settings| {
+                was_bypassed = settings.bypassed;
+                settings.bypassed = bypassed
+            })?;
+        if was_bypassed != bypassed {
+            self.history_manager.record(ChainEdit::Bypass {
+                index,
+                from: was_bypassed,
+                to: bypassed,
+            });
+        }
+        self.events_manager
+            .publish(StateEvent::ProcessorBypassed { index, bypassed });
+        Ok(())
+    }
+
+    /// Returns the value the processor settled on, which may differ from the requested one
+    pub fn set_parameter(
+        &self,
+        index: usize,
+        parameter: Parameter,
+        value: ParameterValue,
+    ) -> Result<ParameterValue, Error> {
+        let mut previous_value = None;
+        let mut new_value = None;
+        self.dsp_manager
+            .lock()
+            .unwrap()
+            .transform_processor(index, |processor| {
+                previous_value = processor.get_parameter(parameter);
+                processor.set_parameter(parameter, value);
+                new_value = processor.get_parameter(parameter);
+            })?;
+        let (Some(previous_value), Some(value)) = (previous_value, new_value) else {
+            return Err(Error::UnsupportedParameter);
+        };
+        if previous_value != value {
+            self.history_manager.record(ChainEdit::Parameter {
+                index,
+                parameter,
+                from: previous_value,
+                to: value.clone(),
+            });
+        }
+        self.events_manager.publish(StateEvent::ParameterChanged {
+            index,
+            parameter,
+            value: value.clone(),
+        });
+        Ok(value)
+    }
+}
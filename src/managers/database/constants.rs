@@ -1,2 +1,5 @@
 pub static DATABASE_NAME: &str = "mojo";
 pub static PRESETS_COLLECTION_NAME: &str = "presets";
+pub static PRESET_REVISIONS_COLLECTION_NAME: &str = "preset_revisions";
+pub static SETLISTS_COLLECTION_NAME: &str = "setlists";
+pub static MIDI_MAPPINGS_COLLECTION_NAME: &str = "midi_mappings";
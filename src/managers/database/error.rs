@@ -1,8 +1,11 @@
 use crate::managers::database::error::Error::Generic;
+use std::io;
 
 #[derive(Debug)]
 pub enum Error {
     Generic,
+    /// The stored preset is not at the version the write was based on
+    VersionConflict,
 }
 
 impl From<mongodb::error::Error> for Error {
@@ -10,3 +13,15 @@ impl From<mongodb::error::Error> for Error {
         Generic
     }
 }
+
+impl From<io::Error> for Error {
+    fn from(_: io::Error) -> Self {
+        Generic
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(_: serde_json::Error) -> Self {
+        Generic
+    }
+}
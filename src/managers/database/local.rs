@@ -0,0 +1,437 @@
+use crate::managers::database::constants::{
+    MIDI_MAPPINGS_COLLECTION_NAME, PRESETS_COLLECTION_NAME, PRESET_REVISIONS_COLLECTION_NAME,
+    SETLISTS_COLLECTION_NAME,
+};
+use crate::managers::database::error::Error;
+use crate::managers::database::migrations::{migrate_preset, migrate_preset_revision};
+use crate::managers::database::models::{
+    Preset, PresetQuery, PresetRevision, PresetSort, PresetsPage, Setlist, SortOrder,
+};
+use crate::managers::database::store::PresetStore;
+use crate::managers::midi::models::MidiMapping;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs::{create_dir_all, read, rename, write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+struct Collections {
+    presets: Vec<Preset>,
+    preset_revisions: Vec<PresetRevision>,
+    setlists: Vec<Setlist>,
+    midi_mappings: Vec<MidiMapping>,
+}
+
+/// Embedded store keeping one JSON file per collection, so the rig can run without MongoDB
+pub struct LocalPresetStore {
+    collections: Mutex<Collections>,
+    directory_path: PathBuf,
+}
+
+impl LocalPresetStore {
+    pub fn new(directory_path: PathBuf) -> Result<Self, Error> {
+        create_dir_all(&directory_path)?;
+        let collections = Collections {
+            presets: read_collection(&directory_path, PRESETS_COLLECTION_NAME)?
+                .into_iter()
+                .filter_map(migrate_preset)
+                .collect(),
+            preset_revisions: read_collection(&directory_path, PRESET_REVISIONS_COLLECTION_NAME)?
+                .into_iter()
+                .filter_map(migrate_preset_revision)
+                .collect(),
+            setlists: read_collection(&directory_path, SETLISTS_COLLECTION_NAME)?,
+            midi_mappings: read_collection(&directory_path, MIDI_MAPPINGS_COLLECTION_NAME)?,
+        };
+        tracing::info!(
+            "local preset store initialised at path {:?}",
+            directory_path
+        );
+        Ok(Self {
+            collections: Mutex::new(collections),
+            directory_path,
+        })
+    }
+
+    /// Updates a copy of the collection, which only replaces the one in memory once it is
+    /// written, so that a failed write never leaves memory ahead of the disk
+    fn update_collection<T: Clone + Serialize>(
+        &self,
+        collection_name: &str,
+        get_documents: fn(&mut Collections) -> &mut Vec<T>,
+        update: impl FnOnce(&mut Vec<T>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut collections = self.collections.lock().unwrap();
+        let documents = get_documents(&mut collections);
+        let mut updated_documents = documents.clone();
+        update(&mut updated_documents)?;
+        write_collection(&self.directory_path, collection_name, &updated_documents)?;
+        *documents = updated_documents;
+        Ok(())
+    }
+
+    fn update_presets(&self, update: impl FnOnce(&mut Vec<Preset>)) -> Result<(), Error> {
+        self.update_collection(
+            PRESETS_COLLECTION_NAME,
+            |collections| &mut collections.presets,
+            |presets| {
+                update(presets);
+                Ok(())
+            },
+        )
+    }
+
+    fn update_preset_revisions(
+        &self,
+        update: impl FnOnce(&mut Vec<PresetRevision>),
+    ) -> Result<(), Error> {
+        self.update_collection(
+            PRESET_REVISIONS_COLLECTION_NAME,
+            |collections| &mut collections.preset_revisions,
+            |revisions| {
+                update(revisions);
+                Ok(())
+            },
+        )
+    }
+
+    fn update_setlists(&self, update: impl FnOnce(&mut Vec<Setlist>)) -> Result<(), Error> {
+        self.update_collection(
+            SETLISTS_COLLECTION_NAME,
+            |collections| &mut collections.setlists,
+            |setlists| {
+                update(setlists);
+                Ok(())
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl PresetStore for LocalPresetStore {
+    async fn create_indexes(&self) -> Result<(), Error> {
+        // Collections are scanned in memory, there is nothing to index
+        Ok(())
+    }
+
+    async fn remove_duplicate_presets(&self) -> Result<(), Error> {
+        // Saves have always replaced the preset with the same ID
+        Ok(())
+    }
+
+    async fn get_default_preset_id(&self) -> Option<Uuid> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .presets
+            .iter()
+            .find(|preset| preset.is_default)
+            .map(|preset| preset.id)
+    }
+
+    async fn set_default_preset_id(&self, preset_id: Uuid) -> Result<(), Error> {
+        self.update_presets(|presets| {
+            for preset in presets.iter_mut() {
+                preset.is_default = preset.id == preset_id;
+            }
+        })
+    }
+
+    async fn get_presets_list(&self) -> Vec<Preset> {
+        self.collections.lock().unwrap().presets.clone()
+    }
+
+    async fn search_presets(&self, query: &PresetQuery) -> PresetsPage {
+        let mut presets: Vec<Preset> = self
+            .collections
+            .lock()
+            .unwrap()
+            .presets
+            .iter()
+            .filter(|preset| is_preset_matching(preset, query))
+            .cloned()
+            .collect();
+        match query.sort {
+            PresetSort::Name => presets.sort_by_key(|preset| preset.name.to_lowercase()),
+            PresetSort::CreatedAt => presets.sort_by_key(|preset| preset.metadata.created_at),
+            PresetSort::ModifiedAt => presets.sort_by_key(|preset| preset.metadata.modified_at),
+        }
+        if let SortOrder::Descending = query.order {
+            presets.reverse();
+        }
+        let total = presets.len();
+        PresetsPage {
+            presets: presets
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit.unwrap_or(usize::MAX))
+                .collect(),
+            total,
+        }
+    }
+
+    async fn get_preset(&self, uuid: Uuid) -> Option<Preset> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .presets
+            .iter()
+            .find(|preset| preset.id == uuid)
+            .cloned()
+    }
+
+    async fn delete_preset(&self, uuid: Uuid) {
+        let _ = self.update_presets(|presets| presets.retain(|preset| preset.id != uuid));
+        let _ = self.update_preset_revisions(|revisions| {
+            revisions.retain(|revision| revision.preset_id != uuid)
+        });
+    }
+
+    async fn save_preset(&self, preset: Preset, expected_version: u64) -> Result<(), Error> {
+        self.update_collection(
+            PRESETS_COLLECTION_NAME,
+            |collections| &mut collections.presets,
+            |presets| {
+                match presets.iter_mut().find(|current| current.id == preset.id) {
+                    Some(current) if current.metadata.version == expected_version => {
+                        *current = preset
+                    }
+                    None if expected_version == 0 => presets.push(preset),
+                    _ => return Err(Error::VersionConflict),
+                }
+                Ok(())
+            },
+        )
+    }
+
+    async fn get_preset_revisions(&self, preset_id: Uuid) -> Vec<PresetRevision> {
+        let collections = self.collections.lock().unwrap();
+        let mut revisions: Vec<PresetRevision> = collections
+            .preset_revisions
+            .iter()
+            .filter(|revision| revision.preset_id == preset_id)
+            .cloned()
+            .collect();
+        revisions.sort_by_key(|revision| revision.timestamp);
+        revisions
+    }
+
+    async fn get_preset_revision(&self, revision_id: Uuid) -> Option<PresetRevision> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .preset_revisions
+            .iter()
+            .find(|revision| revision.id == revision_id)
+            .cloned()
+    }
+
+    async fn save_preset_revision(&self, revision: &PresetRevision) -> Result<(), Error> {
+        self.update_preset_revisions(|revisions| revisions.push(revision.clone()))
+    }
+
+    async fn delete_old_preset_revisions(
+        &self,
+        preset_id: Uuid,
+        kept_count: usize,
+    ) -> Result<(), Error> {
+        let mut revisions: Vec<(u64, Uuid)> = self
+            .collections
+            .lock()
+            .unwrap()
+            .preset_revisions
+            .iter()
+            .filter(|revision| revision.preset_id == preset_id)
+            .map(|revision| (revision.timestamp, revision.id))
+            .collect();
+        revisions.sort_by_key(|(timestamp, _)| Reverse(*timestamp));
+        let deleted_ids: Vec<Uuid> = revisions
+            .into_iter()
+            .skip(kept_count)
+            .map(|(_, id)| id)
+            .collect();
+        if deleted_ids.is_empty() {
+            return Ok(());
+        }
+        self.update_preset_revisions(|revisions| {
+            revisions.retain(|revision| !deleted_ids.contains(&revision.id))
+        })
+    }
+
+    async fn get_setlists(&self) -> Vec<Setlist> {
+        self.collections.lock().unwrap().setlists.clone()
+    }
+
+    async fn get_setlist(&self, uuid: Uuid) -> Option<Setlist> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .setlists
+            .iter()
+            .find(|setlist| setlist.id == uuid)
+            .cloned()
+    }
+
+    async fn get_active_setlist(&self) -> Option<Setlist> {
+        let collections = self.collections.lock().unwrap();
+        collections
+            .setlists
+            .iter()
+            .find(|setlist| setlist.is_active)
+            .cloned()
+    }
+
+    async fn save_setlist(&self, setlist: &Setlist) -> Result<(), Error> {
+        self.update_setlists(|setlists| {
+            match setlists.iter_mut().find(|current| current.id == setlist.id) {
+                Some(current) => *current = setlist.clone(),
+                None => setlists.push(setlist.clone()),
+            }
+        })
+    }
+
+    async fn delete_setlist(&self, uuid: Uuid) {
+        let _ = self.update_setlists(|setlists| setlists.retain(|setlist| setlist.id != uuid));
+    }
+
+    async fn set_active_setlist_id(&self, setlist_id: Uuid) -> Result<(), Error> {
+        self.update_setlists(|setlists| {
+            for setlist in setlists.iter_mut() {
+                setlist.is_active = setlist.id == setlist_id;
+            }
+        })
+    }
+
+    async fn set_setlist_position(&self, setlist_id: Uuid, position: usize) -> Result<(), Error> {
+        self.update_setlists(|setlists| {
+            if let Some(setlist) = setlists.iter_mut().find(|setlist| setlist.id == setlist_id) {
+                setlist.position = position;
+            }
+        })
+    }
+
+    async fn get_midi_mappings(&self) -> Vec<MidiMapping> {
+        self.collections.lock().unwrap().midi_mappings.clone()
+    }
+
+    async fn save_midi_mappings(&self, midi_mappings: &[MidiMapping]) -> Result<(), Error> {
+        self.update_collection(
+            MIDI_MAPPINGS_COLLECTION_NAME,
+            |collections| &mut collections.midi_mappings,
+            |mappings| {
+                *mappings = midi_mappings.to_vec();
+                Ok(())
+            },
+        )
+    }
+}
+
+fn is_preset_matching(preset: &Preset, query: &PresetQuery) -> bool {
+    let metadata = &preset.metadata;
+    let is_tag_matching = query.tag.as_ref().is_none_or(|tag| {
+        let tag = tag.to_lowercase();
+        metadata
+            .tags
+            .iter()
+            .any(|preset_tag| preset_tag.to_lowercase() == tag)
+    });
+    let is_favourite_matching = query
+        .is_favourite
+        .is_none_or(|is_favourite| metadata.is_favourite == is_favourite);
+    let is_text_matching = query.text.as_ref().is_none_or(|text| {
+        let text = text.to_lowercase();
+        [&metadata.author, &metadata.description]
+            .into_iter()
+            .flatten()
+            .chain(metadata.tags.iter())
+            .chain([&preset.name])
+            .any(|value| value.to_lowercase().contains(&text))
+    });
+    is_tag_matching && is_favourite_matching && is_text_matching
+}
+
+fn get_collection_path(directory_path: &Path, collection_name: &str) -> PathBuf {
+    directory_path.join(format!("{}.json", collection_name))
+}
+
+fn read_collection<T: DeserializeOwned>(
+    directory_path: &Path,
+    collection_name: &str,
+) -> Result<Vec<T>, Error> {
+    let path = get_collection_path(directory_path, collection_name);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    Ok(serde_json::from_slice(&read(path)?)?)
+}
+
+/// Writes to a temporary file first, so a crash mid-write never leaves a truncated collection
+fn write_collection<T: Serialize>(
+    directory_path: &Path,
+    collection_name: &str,
+    documents: &[T],
+) -> Result<(), Error> {
+    let path = get_collection_path(directory_path, collection_name);
+    let temporary_path = path.with_extension("json.tmp");
+    write(&temporary_path, serde_json::to_vec_pretty(documents)?)?;
+    rename(temporary_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::database::models::PresetMetadata;
+
+    fn create_preset() -> Preset {
+        Preset {
+            schema_version: 0,
+            id: Uuid::new_v4(),
+            is_default: false,
+            name: "Lead".to_string(),
+            processors: vec![],
+            tempo: 120.0,
+            scenes: vec![],
+            expression_assignments: vec![],
+            metadata: PresetMetadata::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn stale_preset_save_is_rejected() {
+        let directory_path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let store = LocalPresetStore::new(directory_path.clone()).unwrap();
+        let mut preset = create_preset();
+
+        preset.metadata.version = 1;
+        store.save_preset(preset.clone(), 0).await.unwrap();
+        let new_preset_result = store.save_preset(preset.clone(), 0).await;
+        preset.metadata.version = 2;
+        store.save_preset(preset.clone(), 1).await.unwrap();
+        let stale_save_result = store.save_preset(preset.clone(), 1).await;
+        let stored_version = store.get_preset(preset.id).await.unwrap().metadata.version;
+        let _ = std::fs::remove_dir_all(directory_path);
+
+        assert!(matches!(new_preset_result, Err(Error::VersionConflict)));
+        assert!(matches!(stale_save_result, Err(Error::VersionConflict)));
+        assert_eq!(stored_version, 2);
+    }
+
+    #[tokio::test]
+    async fn failed_write_leaves_the_collection_unchanged() {
+        let directory_path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let store = LocalPresetStore::new(directory_path.clone()).unwrap();
+        // The temporary file cannot be written over a directory
+        create_dir_all(
+            get_collection_path(&directory_path, PRESETS_COLLECTION_NAME)
+                .with_extension("json.tmp"),
+        )
+        .unwrap();
+
+        let save_result = store.save_preset(create_preset(), 0).await;
+        let presets_count = store.get_presets_list().await.len();
+        let _ = std::fs::remove_dir_all(directory_path);
+
+        assert!(save_result.is_err());
+        assert_eq!(presets_count, 0);
+    }
+}
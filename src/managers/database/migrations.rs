@@ -0,0 +1,315 @@
+use crate::managers::database::models::{Preset, PresetMetadata, PresetRevision};
+use crate::managers::dsp::models::ProcessorType;
+use crate::managers::dsp::transport::DEFAULT_TEMPO;
+use crate::processors::frontline::models::Parameter;
+use serde_json::{json, Map, Value};
+
+pub static PRESET_SCHEMA_VERSION: u32 = 3;
+
+/// Upgrades a preset document from `from_version` to the next one
+struct Migration {
+    from_version: u32,
+    migrate: fn(&mut Map<String, Value>),
+}
+
+/// Ordered by `from_version`. When a processor, a parameter or a range changes, append
+/// a migration here and bump `PRESET_SCHEMA_VERSION`.
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 0,
+        migrate: add_tempo_and_scenes,
+    },
+    Migration {
+        from_version: 1,
+        migrate: add_metadata,
+    },
+    Migration {
+        from_version: 2,
+        migrate: rename_rotary_spread,
+    },
+];
+
+/// Brings a stored preset document to the current schema. Unknown processors and
+/// parameters are dropped with a warning, so one stale entry never hides the whole preset.
+pub fn migrate_preset(mut document: Value) -> Option<Preset> {
+    let Some(fields) = document.as_object_mut() else {
+        tracing::warn!("preset document is not an object, skipping it");
+        return None;
+    };
+    let schema_version = fields
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or_default() as u32;
+    if schema_version > PRESET_SCHEMA_VERSION {
+        tracing::warn!(
+            "preset schema version {} is newer than {}, loading it anyway",
+            schema_version,
+            PRESET_SCHEMA_VERSION
+        );
+    }
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.from_version >= schema_version)
+    {
+        (migration.migrate)(fields);
+    }
+    remove_unknown_processors(fields);
+    fields.insert("schema_version".to_string(), json!(PRESET_SCHEMA_VERSION));
+
+    match serde_json::from_value(document) {
+        Ok(preset) => Some(preset),
+        Err(error) => {
+            tracing::warn!("cannot read preset document, skipping it: {}", error);
+            None
+        }
+    }
+}
+
+/// Same as `migrate_preset`, for the preset embedded in a revision
+pub fn migrate_preset_revision(mut document: Value) -> Option<PresetRevision> {
+    let preset = migrate_preset(document.get_mut("preset")?.take())?;
+    document["preset"] = serde_json::to_value(preset).ok()?;
+    match serde_json::from_value(document) {
+        Ok(revision) => Some(revision),
+        Err(error) => {
+            tracing::warn!(
+                "cannot read preset revision document, skipping it: {}",
+                error
+            );
+            None
+        }
+    }
+}
+
+fn add_tempo_and_scenes(fields: &mut Map<String, Value>) {
+    fields
+        .entry("tempo")
+        .or_insert_with(|| json!(DEFAULT_TEMPO));
+    fields.entry("scenes").or_insert_with(|| json!([]));
+}
+
+fn add_metadata(fields: &mut Map<String, Value>) {
+    fields
+        .entry("metadata")
+        .or_insert_with(|| json!(PresetMetadata::default()));
+}
+
+/// The rotary is mono, its `spread` was only ever the angle of the summed microphones
+fn rename_rotary_spread(fields: &mut Map<String, Value>) {
+    let is_rotary = |processor: &Value| processor.get("processor_type") == Some(&json!("rotary"));
+    let rename_parameter = |processor: &mut Value| {
+        if !is_rotary(processor) {
+            return;
+        }
+        if let Some(Value::Object(values)) = processor.pointer_mut("/parameters/numeric") {
+            if let Some(value) = values.remove("spread") {
+                values.insert("mic_angle".to_string(), value);
+            }
+        }
+    };
+
+    let mut rotary_indices = vec![];
+    if let Some(Value::Array(processors)) = fields.get_mut("processors") {
+        for (index, processor) in processors.iter_mut().enumerate() {
+            if is_rotary(processor) {
+                rotary_indices.push(index as u64);
+            }
+            rename_parameter(processor);
+        }
+    }
+    if let Some(Value::Array(scenes)) = fields.get_mut("scenes") {
+        for scene in scenes {
+            if let Some(Value::Array(processors)) = scene.get_mut("processors") {
+                processors.iter_mut().for_each(rename_parameter);
+            }
+        }
+    }
+    if let Some(Value::Array(assignments)) = fields.get_mut("expression_assignments") {
+        for assignment in assignments {
+            let is_rotary_assignment = assignment
+                .get("processor_index")
+                .and_then(Value::as_u64)
+                .is_some_and(|index| rotary_indices.contains(&index));
+            if is_rotary_assignment && assignment.get("parameter") == Some(&json!("spread")) {
+                assignment["parameter"] = json!("mic_angle");
+            }
+        }
+    }
+}
+
+fn remove_unknown_processors(fields: &mut Map<String, Value>) {
+    let Some(Value::Array(processors)) = fields.get_mut("processors") else {
+        return;
+    };
+    // Scenes mirror the chain by index, so their entries are dropped at the same positions
+    let unknown_indices: Vec<usize> = processors
+        .iter()
+        .enumerate()
+        .filter(|(_, processor)| !is_known_processor(processor))
+        .map(|(index, _)| index)
+        .collect();
+    remove_indices(processors, &unknown_indices);
+    processors.iter_mut().for_each(remove_unknown_parameters);
+    remove_stale_expression_assignments(fields, &unknown_indices);
+
+    let Some(Value::Array(scenes)) = fields.get_mut("scenes") else {
+        return;
+    };
+    for scene in scenes {
+        if let Some(Value::Array(processors)) = scene.get_mut("processors") {
+            remove_indices(processors, &unknown_indices);
+            processors.retain(is_known_processor);
+            processors.iter_mut().for_each(remove_unknown_parameters);
+        }
+    }
+}
+
+/// Assignments refer to processors by index, so they follow the processors removed before them
+fn remove_stale_expression_assignments(fields: &mut Map<String, Value>, removed_indices: &[usize]) {
+    let Some(Value::Array(assignments)) = fields.get_mut("expression_assignments") else {
+        return;
+    };
+    assignments.retain_mut(|assignment| {
+        let parameter = assignment.get("parameter").cloned().unwrap_or_default();
+        if serde_json::from_value::<Parameter>(parameter.clone()).is_err() {
+            tracing::warn!("unknown expression parameter {}, skipping it", parameter);
+            return false;
+        }
+        let Some(processor_index) = assignment.get("processor_index").and_then(Value::as_u64)
+        else {
+            return true;
+        };
+        let processor_index = processor_index as usize;
+        if removed_indices.contains(&processor_index) {
+            return false;
+        }
+        let removed_before_count = removed_indices
+            .iter()
+            .filter(|index| **index < processor_index)
+            .count();
+        assignment["processor_index"] = json!(processor_index - removed_before_count);
+        true
+    });
+}
+
+fn is_known_processor(processor: &Value) -> bool {
+    let processor_type = processor.get("processor_type").cloned().unwrap_or_default();
+    let is_known = serde_json::from_value::<ProcessorType>(processor_type.clone()).is_ok();
+    if !is_known {
+        tracing::warn!("unknown processor type {}, skipping it", processor_type);
+    }
+    is_known
+}
+
+fn remove_unknown_parameters(processor: &mut Value) {
+    let Some(Value::Object(parameters)) = processor.get_mut("parameters") else {
+        return;
+    };
+    for values in parameters.values_mut() {
+        let Value::Object(values) = values else {
+            continue;
+        };
+        values.retain(|parameter, _| {
+            let is_known = serde_json::from_value::<Parameter>(json!(parameter)).is_ok();
+            if !is_known {
+                tracing::warn!("unknown parameter {}, skipping it", parameter);
+            }
+            is_known
+        });
+    }
+}
+
+fn remove_indices(values: &mut Vec<Value>, indices: &[usize]) {
+    for index in indices.iter().rev() {
+        if *index < values.len() {
+            values.remove(*index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_processor(processor_type: &str) -> Value {
+        json!({
+            "processor_type": processor_type,
+            "settings": { "bypassed": false },
+            "parameters": { "numeric": { "drive": 0.5 }, "string": {} },
+        })
+    }
+
+    fn create_assignment(processor_index: usize, parameter: &str) -> Value {
+        json!({
+            "processor_index": processor_index,
+            "parameter": parameter,
+            "minimum": 0.0,
+            "maximum": 1.0,
+        })
+    }
+
+    #[test]
+    fn expression_assignments_follow_removed_processors() {
+        let document = json!({
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "is_default": false,
+            "name": "Lead",
+            "processors": [
+                create_processor("clean_booster"),
+                create_processor("unknown_pedal"),
+                create_processor("simple_distortion"),
+            ],
+            "expression_assignments": [
+                create_assignment(0, "drive"),
+                create_assignment(1, "drive"),
+                create_assignment(2, "drive"),
+                create_assignment(2, "unknown_parameter"),
+            ],
+        });
+
+        let preset = migrate_preset(document).unwrap();
+
+        let processor_indices: Vec<usize> = preset
+            .expression_assignments
+            .iter()
+            .map(|assignment| assignment.processor_index)
+            .collect();
+        assert_eq!(preset.processors.len(), 2);
+        assert_eq!(processor_indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn rotary_spread_becomes_mic_angle() {
+        let mut rotary = create_processor("rotary");
+        rotary["parameters"]["numeric"] = json!({ "spread": 0.25 });
+        let document = json!({
+            "schema_version": 2,
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "is_default": false,
+            "name": "Organ",
+            "tempo": 120.0,
+            "processors": [rotary.clone()],
+            "scenes": [{ "name": "Chorus", "processors": [rotary] }],
+            "expression_assignments": [create_assignment(0, "spread")],
+            "metadata": PresetMetadata::default(),
+        });
+
+        let preset = migrate_preset(document).unwrap();
+
+        assert_eq!(
+            preset.processors[0]
+                .parameters
+                .numeric
+                .get(&Parameter::MicAngle),
+            Some(&0.25)
+        );
+        assert_eq!(
+            preset.scenes[0].processors[0]
+                .parameters
+                .numeric
+                .get(&Parameter::MicAngle),
+            Some(&0.25)
+        );
+        assert!(preset.expression_assignments[0].parameter == Parameter::MicAngle);
+    }
+}
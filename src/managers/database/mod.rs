@@ -1,80 +1,56 @@
-use crate::managers::database::constants::{DATABASE_NAME, PRESETS_COLLECTION_NAME};
 use crate::managers::database::error::Error;
-use crate::managers::database::models::Preset;
+use crate::managers::database::local::LocalPresetStore;
+use crate::managers::database::models::Storage;
+use crate::managers::database::mongo::MongoPresetStore;
+use crate::managers::database::store::PresetStore;
+use crate::managers::file_system::FileSystemManager;
 use crate::settings::Settings;
-use futures::TryStreamExt;
-use mongodb::bson::doc;
-use mongodb::{Client, Collection, Database};
-use uuid::Uuid;
+use std::ops::Deref;
+use std::sync::Arc;
 
 mod constants;
-mod error;
+pub mod error;
+mod local;
+pub mod migrations;
 pub mod models;
+mod mongo;
+pub mod store;
 
 #[derive(Clone)]
 pub struct DatabaseManager {
-    database: Database,
+    store: Arc<dyn PresetStore>,
 }
 
 impl DatabaseManager {
-    pub async fn new(settings: &Settings) -> Result<Self, Error> {
-        let client = Client::with_uri_str(settings.database_connection_string.clone()).await?;
-        tracing::info!("MongoDB client initialised");
-        Ok(Self {
-            database: client.database(DATABASE_NAME),
-        })
-    }
-
-    pub async fn get_default_preset_id(&self) -> Option<Uuid> {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        presets
-            .find_one(doc! { "isDefault": true }, None)
-            .await
-            .unwrap_or_default()
-            .map(|preset| preset.id)
-    }
-
-    pub async fn set_default_preset_id(&self, preset_id: Uuid) -> Result<(), Error> {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        presets
-            .update_many(doc! {}, doc! { "$set": {"isDefault": false} }, None)
-            .await?;
-        presets
-            .update_one(
-                doc! {"id": preset_id.to_string()},
-                doc! {"$set": {"isDefault": true}},
-                None,
-            )
-            .await?;
-        Ok(())
-    }
-
-    pub async fn get_presets_list(&self) -> Vec<Preset> {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        let cursor = match presets.find(doc! {}, None).await {
-            Ok(cursor) => cursor,
-            Err(_) => return vec![],
+    pub async fn new(
+        settings: &Settings,
+        file_system_manager: &FileSystemManager,
+    ) -> Result<Self, Error> {
+        let store: Arc<dyn PresetStore> = match settings.storage {
+            Storage::Local => Arc::new(LocalPresetStore::new(
+                file_system_manager.get_data_root_path().join("database"),
+            )?),
+            Storage::Mongo => {
+                Arc::new(MongoPresetStore::new(settings.database_connection_string.clone()).await?)
+            }
         };
-        cursor.try_collect().await.unwrap_or_default()
-    }
-
-    pub async fn get_preset(&self, uuid: Uuid) -> Option<Preset> {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        presets
-            .find_one(doc! { "id": uuid.to_string() }, None)
-            .await
-            .unwrap_or_default()
+        if settings.remove_duplicate_presets {
+            if let Err(error) = store.remove_duplicate_presets().await {
+                tracing::error!("cannot remove duplicate presets: {:?}", error);
+            }
+        }
+        // Queries still work without indexes, only slower
+        if let Err(error) = store.create_indexes().await {
+            tracing::error!("cannot create database indexes: {:?}", error);
+        }
+        Ok(Self { store })
     }
+}
 
-    pub async fn delete_preset(&self, uuid: Uuid) {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        let _ = presets
-            .delete_many(doc! { "id": uuid.to_string() }, None)
-            .await;
-    }
+impl Deref for DatabaseManager {
+    type Target = dyn PresetStore;
 
-    pub async fn save_preset(&self, preset: Preset) {
-        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
-        let _ = presets.insert_one(preset, None).await;
+    fn deref(&self) -> &Self::Target {
+        self.store.as_ref()
     }
 }
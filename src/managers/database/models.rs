@@ -1,11 +1,117 @@
-use crate::managers::dsp::models::ProcessorInfo;
+use crate::managers::dsp::models::{ExpressionAssignment, ProcessorInfo};
+use crate::managers::dsp::transport::DEFAULT_TEMPO;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Preset {
+    #[serde(default)]
+    pub schema_version: u32,
     pub id: Uuid,
     pub is_default: bool,
     pub name: String,
     pub processors: Vec<ProcessorInfo>,
+    #[serde(default = "get_default_tempo")]
+    pub tempo: f32,
+    #[serde(default)]
+    pub scenes: Vec<Scene>,
+    /// Refer to the processors by their position in the chain
+    #[serde(default)]
+    pub expression_assignments: Vec<ExpressionAssignment>,
+    pub metadata: PresetMetadata,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PresetMetadata {
+    pub tags: Vec<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub created_at: u64,
+    /// Milliseconds since the Unix epoch
+    pub modified_at: u64,
+    pub is_favourite: bool,
+    /// Guitar and pickup the preset was dialled in with
+    pub guitar_notes: Option<String>,
+    /// Incremented on every write, 0 until the preset is first stored
+    #[serde(default)]
+    pub version: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetSort {
+    #[default]
+    Name,
+    CreatedAt,
+    ModifiedAt,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Filters are combined, `text` matches name, author, description and tags ignoring case
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct PresetQuery {
+    pub tag: Option<String>,
+    pub text: Option<String>,
+    pub is_favourite: Option<bool>,
+    #[serde(default)]
+    pub sort: PresetSort,
+    #[serde(default)]
+    pub order: SortOrder,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+pub struct PresetsPage {
+    pub presets: Vec<Preset>,
+    /// Matching presets before pagination
+    pub total: usize,
+}
+
+/// A saved version of a preset, kept so that any previous save can be restored
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PresetRevision {
+    pub id: Uuid,
+    pub preset_id: Uuid,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub message: Option<String>,
+    pub preset: Preset,
+}
+
+/// Ordered presets for a gig, the active one is navigated with next/previous
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Setlist {
+    pub id: Uuid,
+    pub name: String,
+    pub preset_ids: Vec<Uuid>,
+    pub is_active: bool,
+    pub position: usize,
+}
+
+/// Parameters and bypass states to apply on top of the preset chain
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Scene {
+    pub name: String,
+    pub processors: Vec<ProcessorInfo>,
+}
+
+/// Backend holding presets, setlists and MIDI mappings
+#[derive(ValueEnum, Clone, Debug)]
+pub enum Storage {
+    Local,
+    Mongo,
+}
+
+fn get_default_tempo() -> f32 {
+    DEFAULT_TEMPO
 }
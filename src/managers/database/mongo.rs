@@ -0,0 +1,647 @@
+use crate::managers::database::constants::{
+    DATABASE_NAME, MIDI_MAPPINGS_COLLECTION_NAME, PRESETS_COLLECTION_NAME,
+    PRESET_REVISIONS_COLLECTION_NAME, SETLISTS_COLLECTION_NAME,
+};
+use crate::managers::database::error::Error;
+use crate::managers::database::migrations::{migrate_preset, migrate_preset_revision};
+use crate::managers::database::models::{
+    Preset, PresetQuery, PresetRevision, PresetSort, PresetsPage, Setlist, SortOrder,
+};
+use crate::managers::database::store::PresetStore;
+use crate::managers::midi::models::MidiMapping;
+use crate::managers::presets::get_timestamp;
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use mongodb::bson::spec::BinarySubtype;
+use mongodb::bson::{doc, Binary, Bson, Document};
+use mongodb::error::{ErrorKind, WriteFailure};
+use mongodb::options::{Collation, CollationStrength, FindOptions, IndexOptions, ReplaceOptions};
+use mongodb::{Client, Collection, Database, IndexModel};
+use serde_json::Value;
+use uuid::Uuid;
+
+// Named apart from the former case-sensitive indexes, which have the same keys
+static NAME_INDEX_NAME: &str = "name_case_insensitive";
+static TAGS_INDEX_NAME: &str = "tags_case_insensitive";
+static DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+pub struct MongoPresetStore {
+    database: Database,
+}
+
+impl MongoPresetStore {
+    pub async fn new(connection_string: String) -> Result<Self, Error> {
+        let client = Client::with_uri_str(connection_string).await?;
+        tracing::info!("MongoDB client initialised");
+        Ok(Self {
+            database: client.database(DATABASE_NAME),
+        })
+    }
+}
+
+#[async_trait]
+impl PresetStore for MongoPresetStore {
+    async fn create_indexes(&self) -> Result<(), Error> {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let unique_options = IndexOptions::builder().unique(true).build();
+        presets
+            .create_indexes(
+                [
+                    IndexModel::builder()
+                        .keys(doc! { "id": 1 })
+                        .options(unique_options.clone())
+                        .build(),
+                    IndexModel::builder()
+                        .keys(doc! { "name": 1 })
+                        .options(
+                            IndexOptions::builder()
+                                .name(NAME_INDEX_NAME.to_string())
+                                .collation(get_name_collation())
+                                .build(),
+                        )
+                        .build(),
+                    IndexModel::builder()
+                        .keys(doc! { "metadata.tags": 1 })
+                        .options(
+                            IndexOptions::builder()
+                                .name(TAGS_INDEX_NAME.to_string())
+                                .collation(get_name_collation())
+                                .build(),
+                        )
+                        .build(),
+                    IndexModel::builder()
+                        .keys(doc! { "metadata.created_at": 1 })
+                        .build(),
+                    IndexModel::builder()
+                        .keys(doc! { "metadata.modified_at": 1 })
+                        .build(),
+                ],
+                None,
+            )
+            .await
+            .inspect_err(|error| {
+                if is_duplicate_key_error(error) {
+                    // Saves used to insert a new copy every time
+                    tracing::error!(
+                        "several presets share an ID, restart with --remove-duplicate-presets"
+                    );
+                }
+            })?;
+        let revisions: Collection<Document> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        revisions
+            .create_indexes(
+                [
+                    IndexModel::builder()
+                        .keys(doc! { "id": 1 })
+                        .options(unique_options)
+                        .build(),
+                    IndexModel::builder()
+                        .keys(doc! { "preset_id": 1, "timestamp": 1 })
+                        .build(),
+                ],
+                None,
+            )
+            .await?;
+        tracing::info!("MongoDB indexes created");
+        Ok(())
+    }
+
+    async fn remove_duplicate_presets(&self) -> Result<(), Error> {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let revisions: Collection<Document> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        let duplicates: Vec<Document> = presets
+            .aggregate(
+                [
+                    doc! { "$sort": { "metadata.modified_at": -1 } },
+                    doc! { "$group": { "_id": "$id", "documents": { "$push": "$$ROOT" } } },
+                    doc! { "$match": { "documents.1": { "$exists": true } } },
+                ],
+                None,
+            )
+            .await?
+            .try_collect()
+            .await?;
+        let timestamp = get_timestamp() as i64;
+        for duplicate in duplicates {
+            let Ok(documents) = duplicate.get_array("documents") else {
+                continue;
+            };
+            let preset_id = duplicate.get("_id").cloned().unwrap_or(Bson::Null);
+            let mut object_ids = vec![];
+            let mut backups = vec![];
+            for document in documents[1..].iter().filter_map(Bson::as_document) {
+                let mut document = document.clone();
+                object_ids.extend(document.remove("_id"));
+                backups.push(doc! {
+                    "id": get_uuid_bson(Uuid::new_v4()),
+                    "preset_id": preset_id.clone(),
+                    "timestamp": timestamp,
+                    "message": "Duplicate copy removed",
+                    "preset": document,
+                });
+            }
+            if backups.is_empty() {
+                continue;
+            }
+            revisions.insert_many(backups, None).await?;
+            let result = presets
+                .delete_many(doc! { "_id": { "$in": object_ids } }, None)
+                .await?;
+            tracing::warn!(
+                "moved {} duplicate copies of preset {} to its revisions",
+                result.deleted_count,
+                get_json_from_bson(preset_id)
+            );
+        }
+        Ok(())
+    }
+
+    async fn get_default_preset_id(&self) -> Option<Uuid> {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        presets
+            .find_one(doc! { "is_default": true }, None)
+            .await
+            .unwrap_or_default()
+            .and_then(get_preset_from_document)
+            .map(|preset| preset.id)
+    }
+
+    async fn set_default_preset_id(&self, preset_id: Uuid) -> Result<(), Error> {
+        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
+        presets
+            .update_many(doc! {}, doc! { "$set": {"is_default": false} }, None)
+            .await?;
+        presets
+            .update_one(
+                doc! {"id": get_uuid_bson(preset_id)},
+                doc! {"$set": {"is_default": true}},
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_presets_list(&self) -> Vec<Preset> {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let cursor = match presets.find(doc! {}, None).await {
+            Ok(cursor) => cursor,
+            Err(_) => return vec![],
+        };
+        let documents: Vec<Document> = cursor.try_collect().await.unwrap_or_default();
+        documents
+            .into_iter()
+            .filter_map(get_preset_from_document)
+            .collect()
+    }
+
+    async fn search_presets(&self, query: &PresetQuery) -> PresetsPage {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let filter = get_presets_filter(query);
+        let total = presets
+            .count_documents(filter.clone(), None)
+            .await
+            .unwrap_or_default() as usize;
+        let sort_field = match query.sort {
+            PresetSort::Name => "name",
+            PresetSort::CreatedAt => "metadata.created_at",
+            PresetSort::ModifiedAt => "metadata.modified_at",
+        };
+        let sort_direction = match query.order {
+            SortOrder::Ascending => 1,
+            SortOrder::Descending => -1,
+        };
+        let options = FindOptions::builder()
+            .sort(doc! { sort_field: sort_direction })
+            .collation(get_name_collation())
+            .skip(query.offset as u64)
+            .limit(query.limit.map(|limit| limit as i64))
+            .build();
+        let cursor = match presets.find(filter, options).await {
+            Ok(cursor) => cursor,
+            Err(_) => {
+                return PresetsPage {
+                    presets: vec![],
+                    total,
+                }
+            }
+        };
+        // Unreadable documents still count in the total and the offsets, they are only left out
+        // of their page
+        let presets = cursor
+            .filter_map(|document| async move {
+                let preset = document.ok().and_then(get_preset_from_document);
+                if preset.is_none() {
+                    tracing::warn!("cannot read a stored preset, skipping it");
+                }
+                preset
+            })
+            .collect()
+            .await;
+        PresetsPage { presets, total }
+    }
+
+    async fn get_preset(&self, uuid: Uuid) -> Option<Preset> {
+        let presets: Collection<Document> = self.database.collection(PRESETS_COLLECTION_NAME);
+        presets
+            .find_one(doc! { "id": get_uuid_bson(uuid) }, None)
+            .await
+            .unwrap_or_default()
+            .and_then(get_preset_from_document)
+    }
+
+    async fn delete_preset(&self, uuid: Uuid) {
+        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let _ = presets
+            .delete_many(doc! { "id": get_uuid_bson(uuid) }, None)
+            .await;
+        let revisions: Collection<PresetRevision> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        let _ = revisions
+            .delete_many(doc! { "preset_id": get_uuid_bson(uuid) }, None)
+            .await;
+    }
+
+    async fn save_preset(&self, preset: Preset, expected_version: u64) -> Result<(), Error> {
+        let presets: Collection<Preset> = self.database.collection(PRESETS_COLLECTION_NAME);
+        let filter = if expected_version == 0 {
+            // Presets stored before versioning have no version field
+            doc! { "id": get_uuid_bson(preset.id), "metadata.version": { "$in": [0, null] } }
+        } else {
+            doc! { "id": get_uuid_bson(preset.id), "metadata.version": expected_version as i64 }
+        };
+        let options = ReplaceOptions::builder()
+            .upsert(expected_version == 0)
+            .build();
+        match presets.replace_one(filter, preset, options).await {
+            Ok(result) if result.matched_count == 0 && result.upserted_id.is_none() => {
+                Err(Error::VersionConflict)
+            }
+            Ok(_) => Ok(()),
+            // A new preset is upserted, which collides with a stored one on the unique index
+            Err(error) if is_duplicate_key_error(&error) => Err(Error::VersionConflict),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn get_preset_revisions(&self, preset_id: Uuid) -> Vec<PresetRevision> {
+        let revisions: Collection<Document> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        let cursor = match revisions
+            .find(
+                doc! { "preset_id": get_uuid_bson(preset_id) },
+                FindOptions::builder().sort(doc! { "timestamp": 1 }).build(),
+            )
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(_) => return vec![],
+        };
+        let documents: Vec<Document> = cursor.try_collect().await.unwrap_or_default();
+        documents
+            .into_iter()
+            .filter_map(get_preset_revision_from_document)
+            .collect()
+    }
+
+    async fn get_preset_revision(&self, revision_id: Uuid) -> Option<PresetRevision> {
+        let revisions: Collection<Document> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        revisions
+            .find_one(doc! { "id": get_uuid_bson(revision_id) }, None)
+            .await
+            .unwrap_or_default()
+            .and_then(get_preset_revision_from_document)
+    }
+
+    async fn save_preset_revision(&self, revision: &PresetRevision) -> Result<(), Error> {
+        let revisions: Collection<PresetRevision> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        revisions.insert_one(revision, None).await?;
+        Ok(())
+    }
+
+    async fn delete_old_preset_revisions(
+        &self,
+        preset_id: Uuid,
+        kept_count: usize,
+    ) -> Result<(), Error> {
+        let revisions: Collection<Document> =
+            self.database.collection(PRESET_REVISIONS_COLLECTION_NAME);
+        let options = FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .skip(kept_count as u64)
+            .projection(doc! { "id": 1 })
+            .build();
+        let documents: Vec<Document> = revisions
+            .find(doc! { "preset_id": get_uuid_bson(preset_id) }, options)
+            .await?
+            .try_collect()
+            .await?;
+        let deleted_ids: Vec<Bson> = documents
+            .into_iter()
+            .filter_map(|mut document| document.remove("id"))
+            .collect();
+        if !deleted_ids.is_empty() {
+            revisions
+                .delete_many(doc! { "id": { "$in": deleted_ids } }, None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_setlists(&self) -> Vec<Setlist> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        let cursor = match setlists.find(doc! {}, None).await {
+            Ok(cursor) => cursor,
+            Err(_) => return vec![],
+        };
+        cursor.try_collect().await.unwrap_or_default()
+    }
+
+    async fn get_setlist(&self, uuid: Uuid) -> Option<Setlist> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        setlists
+            .find_one(doc! { "id": get_uuid_bson(uuid) }, None)
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn get_active_setlist(&self) -> Option<Setlist> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        setlists
+            .find_one(doc! { "is_active": true }, None)
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn save_setlist(&self, setlist: &Setlist) -> Result<(), Error> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        setlists
+            .replace_one(
+                doc! { "id": get_uuid_bson(setlist.id) },
+                setlist,
+                ReplaceOptions::builder().upsert(true).build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_setlist(&self, uuid: Uuid) {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        let _ = setlists
+            .delete_many(doc! { "id": get_uuid_bson(uuid) }, None)
+            .await;
+    }
+
+    async fn set_active_setlist_id(&self, setlist_id: Uuid) -> Result<(), Error> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        setlists
+            .update_many(doc! {}, doc! { "$set": {"is_active": false} }, None)
+            .await?;
+        setlists
+            .update_one(
+                doc! {"id": get_uuid_bson(setlist_id)},
+                doc! {"$set": {"is_active": true}},
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn set_setlist_position(&self, setlist_id: Uuid, position: usize) -> Result<(), Error> {
+        let setlists: Collection<Setlist> = self.database.collection(SETLISTS_COLLECTION_NAME);
+        setlists
+            .update_one(
+                doc! {"id": get_uuid_bson(setlist_id)},
+                doc! {"$set": {"position": position as i64}},
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_midi_mappings(&self) -> Vec<MidiMapping> {
+        let mappings: Collection<MidiMapping> =
+            self.database.collection(MIDI_MAPPINGS_COLLECTION_NAME);
+        let cursor = match mappings.find(doc! {}, None).await {
+            Ok(cursor) => cursor,
+            Err(_) => return vec![],
+        };
+        cursor.try_collect().await.unwrap_or_default()
+    }
+
+    async fn save_midi_mappings(&self, midi_mappings: &[MidiMapping]) -> Result<(), Error> {
+        let mappings: Collection<MidiMapping> =
+            self.database.collection(MIDI_MAPPINGS_COLLECTION_NAME);
+        mappings.delete_many(doc! {}, None).await?;
+        if !midi_mappings.is_empty() {
+            mappings.insert_many(midi_mappings, None).await?;
+        }
+        Ok(())
+    }
+}
+
+fn get_preset_from_document(document: Document) -> Option<Preset> {
+    migrate_preset(get_json_from_bson(Bson::Document(document)))
+}
+
+fn get_preset_revision_from_document(document: Document) -> Option<PresetRevision> {
+    migrate_preset_revision(get_json_from_bson(Bson::Document(document)))
+}
+
+/// Raised by writes colliding on a unique index, and by building one over colliding documents
+fn is_duplicate_key_error(error: &mongodb::error::Error) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == DUPLICATE_KEY_ERROR_CODE
+        }
+        ErrorKind::Command(command_error) => command_error.code == DUPLICATE_KEY_ERROR_CODE,
+        _ => false,
+    }
+}
+
+/// Case-insensitive, like the local store, so both backends sort names and match tags the same way
+fn get_name_collation() -> Collation {
+    Collation::builder()
+        .locale("en".to_string())
+        .strength(CollationStrength::Secondary)
+        .build()
+}
+
+/// The driver serialises `Uuid` as generic binary, so filters must use the same representation
+fn get_uuid_bson(uuid: Uuid) -> Bson {
+    Bson::Binary(Binary {
+        subtype: BinarySubtype::Generic,
+        bytes: uuid.as_bytes().to_vec(),
+    })
+}
+
+/// Documents go through the migrations as JSON. Extended JSON would turn the binary UUIDs into
+/// `$binary` objects, so they are converted back to the strings `Uuid` deserialises from.
+fn get_json_from_bson(value: Bson) -> Value {
+    match value {
+        Bson::Document(document) => Value::Object(
+            document
+                .into_iter()
+                .map(|(key, value)| (key, get_json_from_bson(value)))
+                .collect(),
+        ),
+        Bson::Array(values) => Value::Array(values.into_iter().map(get_json_from_bson).collect()),
+        Bson::Binary(binary) => match Uuid::from_slice(&binary.bytes) {
+            Ok(uuid) if matches!(binary.subtype, BinarySubtype::Generic | BinarySubtype::Uuid) => {
+                Value::String(uuid.to_string())
+            }
+            _ => Bson::Binary(binary).into_relaxed_extjson(),
+        },
+        other => other.into_relaxed_extjson(),
+    }
+}
+
+fn get_presets_filter(query: &PresetQuery) -> Document {
+    let mut filter = doc! {};
+    if let Some(tag) = &query.tag {
+        filter.insert("metadata.tags", tag);
+    }
+    if let Some(is_favourite) = query.is_favourite {
+        filter.insert("metadata.is_favourite", is_favourite);
+    }
+    if let Some(text) = &query.text {
+        let pattern = doc! { "$regex": regex::escape(text), "$options": "i" };
+        filter.insert(
+            "$or",
+            [
+                "name",
+                "metadata.author",
+                "metadata.description",
+                "metadata.tags",
+            ]
+            .into_iter()
+            .map(|field| doc! { field: pattern.clone() })
+            .collect::<Vec<Document>>(),
+        );
+    }
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::database::migrations::PRESET_SCHEMA_VERSION;
+    use crate::managers::database::models::{PresetMetadata, Scene};
+    use crate::managers::dsp::models::{
+        ExpressionAssignment, ProcessorInfo, ProcessorParameters, ProcessorSettings, ProcessorType,
+    };
+    use crate::processors::frontline::models::Parameter;
+    use mongodb::bson::to_raw_document_buf;
+    use std::collections::HashMap;
+
+    fn create_preset() -> Preset {
+        let processor = ProcessorInfo {
+            processor_type: ProcessorType::CleanBooster,
+            settings: ProcessorSettings { bypassed: true },
+            parameters: ProcessorParameters {
+                numeric: HashMap::from([(Parameter::Drive, 0.25)]),
+                string: HashMap::new(),
+            },
+        };
+        Preset {
+            schema_version: PRESET_SCHEMA_VERSION,
+            id: Uuid::new_v4(),
+            is_default: false,
+            name: "Lead".to_string(),
+            processors: vec![processor.clone()],
+            tempo: 132.5,
+            scenes: vec![Scene {
+                name: "Solo".to_string(),
+                processors: vec![processor],
+            }],
+            expression_assignments: vec![ExpressionAssignment {
+                processor_index: 0,
+                parameter: Parameter::Drive,
+                minimum: 0.2,
+                maximum: 0.8,
+            }],
+            metadata: PresetMetadata {
+                tags: vec!["rock".to_string()],
+                author: Some("someone".to_string()),
+                created_at: 1_700_000_000_000,
+                modified_at: 1_700_000_000_001,
+                ..PresetMetadata::default()
+            },
+        }
+    }
+
+    /// Serialises like the driver does when inserting or replacing a document
+    fn to_stored_document<T: serde::Serialize>(value: &T) -> Document {
+        to_raw_document_buf(value).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn preset_round_trips_through_bson() {
+        let preset = create_preset();
+        let document = to_stored_document(&preset);
+
+        assert_eq!(document.get("id"), Some(&get_uuid_bson(preset.id)));
+        let stored_preset = get_preset_from_document(document).unwrap();
+        assert_eq!(
+            serde_json::to_value(stored_preset).unwrap(),
+            serde_json::to_value(preset).unwrap()
+        );
+    }
+
+    #[test]
+    fn preset_revision_round_trips_through_bson() {
+        let preset = create_preset();
+        let revision = PresetRevision {
+            id: Uuid::new_v4(),
+            preset_id: preset.id,
+            timestamp: 1_700_000_000_002,
+            message: Some("brighter".to_string()),
+            preset,
+        };
+        let document = to_stored_document(&revision);
+
+        assert_eq!(
+            document.get("preset_id"),
+            Some(&get_uuid_bson(revision.preset_id))
+        );
+        let stored_revision = get_preset_revision_from_document(document).unwrap();
+        assert_eq!(
+            serde_json::to_value(stored_revision).unwrap(),
+            serde_json::to_value(revision).unwrap()
+        );
+    }
+
+    /// Needs a running server: `MOJO_TEST_MONGO_URI=mongodb://localhost:27017 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn preset_round_trips_through_mongo() {
+        let connection_string = std::env::var("MOJO_TEST_MONGO_URI").unwrap();
+        let store = MongoPresetStore::new(connection_string).await.unwrap();
+        let mut preset = create_preset();
+
+        preset.metadata.version = 1;
+        store.save_preset(preset.clone(), 0).await.unwrap();
+        preset.name = "Rhythm".to_string();
+        preset.metadata.version = 2;
+        store.save_preset(preset.clone(), 1).await.unwrap();
+        let stale_save_result = store.save_preset(preset.clone(), 1).await;
+        let stored_preset = store.get_preset(preset.id).await;
+        let copies_count = store
+            .get_presets_list()
+            .await
+            .iter()
+            .filter(|current| current.id == preset.id)
+            .count();
+        store.delete_preset(preset.id).await;
+
+        assert_eq!(
+            serde_json::to_value(stored_preset.unwrap()).unwrap(),
+            serde_json::to_value(preset).unwrap()
+        );
+        assert_eq!(copies_count, 1);
+        assert!(matches!(stale_save_result, Err(Error::VersionConflict)));
+    }
+}
@@ -0,0 +1,67 @@
+use crate::managers::database::error::Error;
+use crate::managers::database::models::{
+    Preset, PresetQuery, PresetRevision, PresetsPage, Setlist,
+};
+use crate::managers::midi::models::MidiMapping;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Persistence backend for presets, setlists and MIDI mappings
+#[async_trait]
+pub trait PresetStore: Send + Sync {
+    /// Called once at startup, before any other operation
+    async fn create_indexes(&self) -> Result<(), Error>;
+
+    /// One-off cleanup of presets stored several times under the same ID, as older versions did
+    /// on every save. Keeps the most recently modified copy and saves the others as its revisions.
+    async fn remove_duplicate_presets(&self) -> Result<(), Error>;
+
+    async fn get_default_preset_id(&self) -> Option<Uuid>;
+
+    async fn set_default_preset_id(&self, preset_id: Uuid) -> Result<(), Error>;
+
+    async fn get_presets_list(&self) -> Vec<Preset>;
+
+    async fn search_presets(&self, query: &PresetQuery) -> PresetsPage;
+
+    async fn get_preset(&self, uuid: Uuid) -> Option<Preset>;
+
+    /// Also deletes the revisions of the preset
+    async fn delete_preset(&self, uuid: Uuid);
+
+    /// Replaces the preset with the same ID, or inserts it, as long as the stored one is still
+    /// at `expected_version` (0 when there is none). Fails with `Error::VersionConflict` otherwise.
+    async fn save_preset(&self, preset: Preset, expected_version: u64) -> Result<(), Error>;
+
+    /// Sorted from the oldest to the newest
+    async fn get_preset_revisions(&self, preset_id: Uuid) -> Vec<PresetRevision>;
+
+    async fn get_preset_revision(&self, revision_id: Uuid) -> Option<PresetRevision>;
+
+    async fn save_preset_revision(&self, revision: &PresetRevision) -> Result<(), Error>;
+
+    /// Keeps only the newest `kept_count` revisions of the preset
+    async fn delete_old_preset_revisions(
+        &self,
+        preset_id: Uuid,
+        kept_count: usize,
+    ) -> Result<(), Error>;
+
+    async fn get_setlists(&self) -> Vec<Setlist>;
+
+    async fn get_setlist(&self, uuid: Uuid) -> Option<Setlist>;
+
+    async fn get_active_setlist(&self) -> Option<Setlist>;
+
+    async fn save_setlist(&self, setlist: &Setlist) -> Result<(), Error>;
+
+    async fn delete_setlist(&self, uuid: Uuid);
+
+    async fn set_active_setlist_id(&self, setlist_id: Uuid) -> Result<(), Error>;
+
+    async fn set_setlist_position(&self, setlist_id: Uuid, position: usize) -> Result<(), Error>;
+
+    async fn get_midi_mappings(&self) -> Vec<MidiMapping>;
+
+    async fn save_midi_mappings(&self, midi_mappings: &[MidiMapping]) -> Result<(), Error>;
+}
@@ -0,0 +1,195 @@
+use ringbuf::consumer::Consumer;
+use rubato::{FastFixedOut, PolynomialDegree, Resampler};
+
+// Clocks of separate devices drift by tens of ppm, so a 1% range leaves plenty of room
+static MAX_RATIO_ADJUSTMENT: f64 = 0.01;
+// The fill is a sawtooth following the callbacks, so it is averaged over ~100 of them
+static FILL_SMOOTHING: f64 = 0.01;
+// The error is counted in blocks, so the loop responds the same for any latency: these gains
+// settle a drift within tens of seconds at 256 samples, with little overshoot
+static PROPORTIONAL_GAIN: f64 = 2e-3;
+static INTEGRAL_GAIN: f64 = 1e-6;
+
+/// Moves samples from the input ring buffer to the output callback, converting the input sample
+/// rate to the output one. The ratio is continuously adjusted on the ring buffer fill, so that
+/// the latency stays constant even when the two clocks drift apart.
+pub struct AdaptiveResampler {
+    average_fill: f64,
+    buffer_size: f64,
+    integral: f64,
+    input_buffer: Vec<Vec<f32>>,
+    output_buffer: Vec<Vec<f32>>,
+    output_length: usize,
+    output_position: usize,
+    resampler: FastFixedOut<f32>,
+    target_fill: f64,
+}
+
+impl AdaptiveResampler {
+    /// The ring buffer fill is kept in the middle of `max_latency_in_samples`, leaving room for
+    /// the clocks to drift either way. It never goes below one input block plus the largest
+    /// resampler chunk, so that the buffer doesn't run dry between two input callbacks.
+    pub fn new(
+        input_sample_rate: u32,
+        output_sample_rate: u32,
+        buffer_size: usize,
+        max_latency_in_samples: usize,
+    ) -> Self {
+        let resampler = FastFixedOut::<f32>::new(
+            output_sample_rate as f64 / input_sample_rate as f64,
+            1.0 + MAX_RATIO_ADJUSTMENT,
+            PolynomialDegree::Cubic,
+            buffer_size,
+            1,
+        )
+        .unwrap();
+        let target_fill =
+            (max_latency_in_samples / 2).max(buffer_size + resampler.input_frames_max());
+        // Buffers are sized for the largest ratio, so the audio thread never allocates
+        let input_buffer = resampler.input_buffer_allocate(true);
+        let output_buffer = resampler.output_buffer_allocate(true);
+
+        Self {
+            average_fill: target_fill as f64,
+            buffer_size: buffer_size as f64,
+            integral: 0.0,
+            input_buffer,
+            output_buffer,
+            output_length: 0,
+            output_position: 0,
+            resampler,
+            target_fill: target_fill as f64,
+        }
+    }
+
+    /// Fills `data` with resampled audio. Returns `false` when the ring buffer ran out of samples.
+    #[inline(always)]
+    pub fn process<C: Consumer<Item = f32>>(&mut self, consumer: &mut C, data: &mut [f32]) -> bool {
+        self.adjust_ratio(consumer.occupied_len());
+
+        let mut is_input_available = true;
+        for sample in data.iter_mut() {
+            if self.output_position == self.output_length {
+                is_input_available &= self.resample_chunk(consumer);
+            }
+            *sample = self.output_buffer[0][self.output_position];
+            self.output_position += 1;
+        }
+        is_input_available
+    }
+
+    /// Proportional-integral control: a fuller buffer means the input clock runs faster than
+    /// the output one, so more input samples are consumed per output sample, and vice versa.
+    fn adjust_ratio(&mut self, fill: usize) {
+        self.average_fill += FILL_SMOOTHING * (fill as f64 - self.average_fill);
+        let error = (self.average_fill - self.target_fill) / self.buffer_size;
+        self.integral = (self.integral + error).clamp(
+            -MAX_RATIO_ADJUSTMENT / INTEGRAL_GAIN,
+            MAX_RATIO_ADJUSTMENT / INTEGRAL_GAIN,
+        );
+        let adjustment = (PROPORTIONAL_GAIN * error + INTEGRAL_GAIN * self.integral)
+            .clamp(-MAX_RATIO_ADJUSTMENT, MAX_RATIO_ADJUSTMENT);
+        let _ = self
+            .resampler
+            .set_resample_ratio_relative(1.0 - adjustment, true);
+    }
+
+    fn resample_chunk<C: Consumer<Item = f32>>(&mut self, consumer: &mut C) -> bool {
+        let input_length = self.resampler.input_frames_next();
+        let input = &mut self.input_buffer[0][..input_length];
+        let read_count = consumer.pop_slice(input);
+        input[read_count..].fill(0.0);
+
+        self.output_length = match self.resampler.process_into_buffer(
+            &self.input_buffer,
+            &mut self.output_buffer,
+            None,
+        ) {
+            Ok((_, output_length)) => output_length,
+            Err(_) => {
+                self.output_buffer[0].fill(0.0);
+                self.output_buffer[0].len()
+            }
+        };
+        self.output_position = 0;
+        read_count == input_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ringbuf::producer::Producer;
+    use ringbuf::traits::{Observer, Split};
+    use ringbuf::HeapRb;
+
+    static BUFFER_SIZE: usize = 256;
+    static MAX_LATENCY_IN_SAMPLES: usize = 4096;
+    static SIMULATED_SECONDS: f64 = 120.0;
+
+    /// Two devices at 48 kHz whose clocks are `drift` apart. Returns the settled average fill,
+    /// the target fill and the number of output blocks that ran dry once the buffer was filled.
+    fn run_drifting_streams(drift: f64) -> (f64, f64, usize) {
+        let sample_rate = 48000;
+        let (mut producer, mut consumer) =
+            HeapRb::<f32>::new(MAX_LATENCY_IN_SAMPLES + BUFFER_SIZE).split();
+        let mut adaptive_resampler = AdaptiveResampler::new(
+            sample_rate,
+            sample_rate,
+            BUFFER_SIZE,
+            MAX_LATENCY_IN_SAMPLES,
+        );
+        let input_period = BUFFER_SIZE as f64 / (sample_rate as f64 * (1.0 + drift));
+        let output_period = BUFFER_SIZE as f64 / sample_rate as f64;
+        let input_block = vec![0.5; BUFFER_SIZE];
+        let mut output_block = vec![0.0; BUFFER_SIZE];
+        let mut next_input_time = 0.0;
+        let mut output_time = 0.0;
+        let mut fills = vec![];
+        let mut dry_blocks_count = 0;
+        while output_time < SIMULATED_SECONDS {
+            while next_input_time <= output_time {
+                producer.push_slice(&input_block);
+                next_input_time += input_period;
+            }
+            fills.push(consumer.occupied_len() as f64);
+            let is_input_available = adaptive_resampler.process(&mut consumer, &mut output_block);
+            if !is_input_available && output_time > 1.0 {
+                dry_blocks_count += 1;
+            }
+            output_time += output_period;
+        }
+        // The fill is a sawtooth, the last 10 seconds are averaged once it has settled
+        let settled_fills = &fills[fills.len() - (10.0 / output_period) as usize..];
+        let average_fill = settled_fills.iter().sum::<f64>() / settled_fills.len() as f64;
+        (
+            average_fill,
+            adaptive_resampler.target_fill,
+            dry_blocks_count,
+        )
+    }
+
+    #[test]
+    fn fill_converges_with_a_faster_input_clock() {
+        let (average_fill, target_fill, dry_blocks_count) = run_drifting_streams(100e-6);
+        assert!(
+            (average_fill - target_fill).abs() < 0.05 * target_fill,
+            "average fill {} for a target of {}",
+            average_fill,
+            target_fill
+        );
+        assert_eq!(dry_blocks_count, 0);
+    }
+
+    #[test]
+    fn fill_converges_with_a_slower_input_clock() {
+        let (average_fill, target_fill, dry_blocks_count) = run_drifting_streams(-100e-6);
+        assert!(
+            (average_fill - target_fill).abs() < 0.05 * target_fill,
+            "average fill {} for a target of {}",
+            average_fill,
+            target_fill
+        );
+        assert_eq!(dry_blocks_count, 0);
+    }
+}
@@ -0,0 +1,37 @@
+use ringbuf::traits::Producer;
+use ringbuf::{HeapCons, HeapProd};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub static INPUT_STAGE: usize = 0;
+pub static OUTPUT_STAGE: usize = usize::MAX;
+
+/// Chain stage right after the processor at `index`
+#[inline(always)]
+pub fn get_processor_stage(index: usize) -> usize {
+    index + 1
+}
+
+/// Where the analyser reads the tapped audio from
+pub struct AnalysisSource {
+    pub consumer: HeapCons<f32>,
+    /// Follows the output streams, which can be rebuilt at a different rate
+    pub sample_rate: Arc<AtomicU32>,
+    pub tap_stage: Arc<AtomicUsize>,
+}
+
+/// Audio thread side of the tap: copies the selected stage into a lock-free ring buffer.
+pub struct AnalysisTap {
+    pub producer: HeapProd<f32>,
+    pub tap_stage: Arc<AtomicUsize>,
+}
+
+impl AnalysisTap {
+    #[inline(always)]
+    pub fn capture(&mut self, stage: usize, data: &[f32]) {
+        if self.tap_stage.load(Ordering::Relaxed) == stage {
+            // Samples that don't fit are dropped: the analyser just missed a few
+            self.producer.push_slice(data);
+        }
+    }
+}
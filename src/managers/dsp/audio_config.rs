@@ -0,0 +1,109 @@
+use crate::managers::dsp::models::{
+    AudioConfig, AudioDevice, AudioHost, Driver, SupportedAudioConfig,
+};
+use crate::managers::file_system::error::Error as FileSystemError;
+use crate::managers::file_system::FileSystemManager;
+use clap::ValueEnum;
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::{host_from_id, SampleFormat, SupportedBufferSize, SupportedStreamConfigRange};
+
+static AUDIO_CONFIG_FILE_NAME: &str = "audio_config.json";
+
+/// Lists the devices of every available driver. Only configs with `f32` samples are
+/// reported, since those are the only ones the streams can be built with.
+pub fn get_audio_hosts() -> Vec<AudioHost> {
+    Driver::value_variants()
+        .iter()
+        .filter_map(|driver| {
+            let host = match host_from_id(driver.host_id()) {
+                Ok(host) => host,
+                Err(error) => {
+                    tracing::debug!("audio host {:?} unavailable: {}", driver, error);
+                    return None;
+                }
+            };
+            let default_input_name = host
+                .default_input_device()
+                .and_then(|device| device.name().ok());
+            let default_output_name = host
+                .default_output_device()
+                .and_then(|device| device.name().ok());
+            let input_devices = host
+                .input_devices()
+                .map(|devices| {
+                    devices
+                        .filter_map(|device| {
+                            let configs = device.supported_input_configs().ok()?;
+                            make_audio_device(&device, configs, &default_input_name)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let output_devices = host
+                .output_devices()
+                .map(|devices| {
+                    devices
+                        .filter_map(|device| {
+                            let configs = device.supported_output_configs().ok()?;
+                            make_audio_device(&device, configs, &default_output_name)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(AudioHost {
+                driver: driver.clone(),
+                input_devices,
+                output_devices,
+            })
+        })
+        .collect()
+}
+
+/// The config chosen at runtime, if any, which takes precedence over the command line
+pub fn load_audio_config(file_system_manager: &FileSystemManager) -> Option<AudioConfig> {
+    let content = file_system_manager.read_file(AUDIO_CONFIG_FILE_NAME).ok()?;
+    match serde_json::from_slice(&content) {
+        Ok(config) => Some(config),
+        Err(error) => {
+            tracing::warn!("cannot read the saved audio config, ignoring it: {}", error);
+            None
+        }
+    }
+}
+
+pub fn save_audio_config(
+    file_system_manager: &FileSystemManager,
+    config: &AudioConfig,
+) -> Result<(), FileSystemError> {
+    let content = serde_json::to_vec_pretty(config).map_err(|_| FileSystemError::Conversion)?;
+    file_system_manager.write_file_atomically(AUDIO_CONFIG_FILE_NAME, &content)?;
+    Ok(())
+}
+
+fn make_audio_device(
+    device: &cpal::Device,
+    configs: impl Iterator<Item = SupportedStreamConfigRange>,
+    default_name: &Option<String>,
+) -> Option<AudioDevice> {
+    let name = device.name().ok()?;
+    Some(AudioDevice {
+        is_default: default_name.as_ref() == Some(&name),
+        name,
+        configs: configs
+            .filter(|config| config.sample_format() == SampleFormat::F32)
+            .map(|config| {
+                let (min_buffer_size, max_buffer_size) = match config.buffer_size() {
+                    SupportedBufferSize::Range { min, max } => (Some(*min), Some(*max)),
+                    SupportedBufferSize::Unknown => (None, None),
+                };
+                SupportedAudioConfig {
+                    channels: config.channels(),
+                    min_sample_rate: config.min_sample_rate().0,
+                    max_sample_rate: config.max_sample_rate().0,
+                    min_buffer_size,
+                    max_buffer_size,
+                }
+            })
+            .collect(),
+    })
+}
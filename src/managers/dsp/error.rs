@@ -13,12 +13,17 @@ pub enum Error {
     NamedDeviceNotFound,
     PauseStream,
     PlayStream,
+    StreamsClosed,
 }
 
 pub enum TransformProcessorError {
     NotFound,
 }
 
+pub enum TempoError {
+    SlavedToMidiClock,
+}
+
 impl From<HostUnavailable> for Error {
     fn from(value: HostUnavailable) -> Self {
         Self::Host(value)
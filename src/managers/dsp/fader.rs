@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+static FADE_DURATION: Duration = Duration::from_millis(10);
+// The fade completes within the next output callbacks, this is just a safety net
+static MAX_FADE_WAIT: Duration = Duration::from_millis(500);
+
+/// Output gain target, written by the control side. The audio thread ramps towards it, so that
+/// starting and stopping the engine never produces a pop.
+pub struct Fader {
+    is_open: AtomicBool,
+    is_silent: AtomicBool,
+}
+
+impl Fader {
+    pub fn new() -> Self {
+        Self {
+            is_open: AtomicBool::new(false),
+            is_silent: AtomicBool::new(true),
+        }
+    }
+
+    pub fn open(&self) {
+        self.is_open.store(true, Ordering::Relaxed);
+        // Until the audio thread catches up, a `close` must not be taken as already faded out
+        self.is_silent.store(false, Ordering::Relaxed);
+    }
+
+    pub fn close(&self) {
+        self.is_open.store(false, Ordering::Relaxed);
+    }
+
+    /// `true` once the ramp has reached silence after `close`
+    pub fn is_silent(&self) -> bool {
+        self.is_silent.load(Ordering::Relaxed)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open.load(Ordering::Relaxed)
+    }
+
+    pub async fn wait_until_silent(&self) {
+        let wait_start = Instant::now();
+        while !self.is_silent() && wait_start.elapsed() < MAX_FADE_WAIT {
+            tokio::time::sleep(FADE_DURATION / 4).await;
+        }
+    }
+
+    /// Same as `wait_until_silent`, for callers outside the async runtime
+    pub fn wait_until_silent_blocking(&self) {
+        let wait_start = Instant::now();
+        while !self.is_silent() && wait_start.elapsed() < MAX_FADE_WAIT {
+            sleep(FADE_DURATION / 4);
+        }
+    }
+}
+
+/// The audio thread side of the `Fader`
+pub struct FaderProbe {
+    fader: Arc<Fader>,
+    gain: f32,
+    step: f32,
+}
+
+impl FaderProbe {
+    pub fn new(fader: Arc<Fader>, sample_rate: u32) -> Self {
+        let fade_samples_count = (FADE_DURATION.as_secs_f32() * sample_rate as f32).max(1.0);
+        Self {
+            fader,
+            gain: 0.0,
+            step: 1.0 / fade_samples_count,
+        }
+    }
+
+    #[inline(always)]
+    pub fn apply(&mut self, data: &mut [f32]) {
+        let target = if self.fader.is_open.load(Ordering::Relaxed) {
+            1.0
+        } else {
+            0.0
+        };
+        if self.gain == target {
+            if target == 0.0 {
+                data.fill(0.0);
+            }
+        } else {
+            for sample in data.iter_mut() {
+                self.gain = if target > self.gain {
+                    (self.gain + self.step).min(target)
+                } else {
+                    (self.gain - self.step).max(target)
+                };
+                *sample *= self.gain;
+            }
+        }
+        self.fader
+            .is_silent
+            .store(self.gain == 0.0, Ordering::Relaxed);
+    }
+}
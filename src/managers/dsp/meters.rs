@@ -0,0 +1,157 @@
+use crate::managers::dsp::models::{MeterReading, MetersReadings};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+static MAX_METERED_PROCESSORS: usize = 64;
+static PEAK_RELEASE_TIME_SECONDS: f32 = 0.3;
+static RMS_WINDOW_SECONDS: f32 = 0.3;
+static CLIP_HOLD_SECONDS: f32 = 2.0;
+static CLIP_THRESHOLD: f32 = 1.0;
+
+#[derive(Default)]
+struct SharedLevel {
+    peak: AtomicU32,
+    rms: AtomicU32,
+    clipping: AtomicBool,
+}
+
+impl SharedLevel {
+    fn get_reading(&self) -> MeterReading {
+        MeterReading {
+            peak: f32::from_bits(self.peak.load(Ordering::Relaxed)),
+            rms: f32::from_bits(self.rms.load(Ordering::Relaxed)),
+            clipping: self.clipping.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Lock-free snapshot of the signal levels, written by the audio thread and read by the API.
+pub struct Meters {
+    input: SharedLevel,
+    output: SharedLevel,
+    processors: Vec<SharedLevel>,
+    processors_count: AtomicUsize,
+}
+
+impl Meters {
+    pub fn new() -> Self {
+        Self {
+            input: SharedLevel::default(),
+            output: SharedLevel::default(),
+            processors: (0..MAX_METERED_PROCESSORS)
+                .map(|_| SharedLevel::default())
+                .collect(),
+            processors_count: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get_readings(&self) -> MetersReadings {
+        let processors_count = self
+            .processors_count
+            .load(Ordering::Relaxed)
+            .min(MAX_METERED_PROCESSORS);
+        MetersReadings {
+            input: self.input.get_reading(),
+            output: self.output.get_reading(),
+            processors: self.processors[..processors_count]
+                .iter()
+                .map(SharedLevel::get_reading)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct LevelMeter {
+    peak: f32,
+    mean_square: f32,
+    clip_hold_remaining: usize,
+}
+
+impl LevelMeter {
+    #[inline(always)]
+    fn measure(&mut self, data: &[f32], sample_rate: f32, level: &SharedLevel) {
+        if data.is_empty() {
+            return;
+        }
+
+        let mut block_peak: f32 = 0.0;
+        let mut block_square_sum = 0.0;
+        for sample in data {
+            block_peak = block_peak.max(sample.abs());
+            block_square_sum += sample * sample;
+        }
+        let block_length = data.len() as f32;
+
+        let peak_release = (-block_length / (PEAK_RELEASE_TIME_SECONDS * sample_rate)).exp();
+        self.peak = block_peak.max(self.peak * peak_release);
+
+        let rms_coefficient = (-block_length / (RMS_WINDOW_SECONDS * sample_rate)).exp();
+        self.mean_square = rms_coefficient * self.mean_square
+            + (1.0 - rms_coefficient) * block_square_sum / block_length;
+
+        if block_peak >= CLIP_THRESHOLD {
+            self.clip_hold_remaining = (CLIP_HOLD_SECONDS * sample_rate) as usize;
+        } else {
+            self.clip_hold_remaining = self.clip_hold_remaining.saturating_sub(data.len());
+        }
+
+        level.peak.store(self.peak.to_bits(), Ordering::Relaxed);
+        level
+            .rms
+            .store(self.mean_square.sqrt().to_bits(), Ordering::Relaxed);
+        level
+            .clipping
+            .store(self.clip_hold_remaining > 0, Ordering::Relaxed);
+    }
+}
+
+/// Audio thread side of the meters: it keeps the ballistics state and never allocates.
+pub struct MetersProbe {
+    meters: Arc<Meters>,
+    input: LevelMeter,
+    output: LevelMeter,
+    processors: Vec<LevelMeter>,
+    sample_rate: f32,
+}
+
+impl MetersProbe {
+    pub fn new(meters: Arc<Meters>, sample_rate: u32) -> Self {
+        Self {
+            meters,
+            input: LevelMeter::default(),
+            output: LevelMeter::default(),
+            processors: vec![LevelMeter::default(); MAX_METERED_PROCESSORS],
+            sample_rate: sample_rate as f32,
+        }
+    }
+
+    #[inline(always)]
+    pub fn measure_input(&mut self, data: &[f32]) {
+        self.input
+            .measure(data, self.sample_rate, &self.meters.input);
+    }
+
+    #[inline(always)]
+    pub fn measure_processor(&mut self, index: usize, data: &[f32]) {
+        if let (Some(level_meter), Some(level)) = (
+            self.processors.get_mut(index),
+            self.meters.processors.get(index),
+        ) {
+            level_meter.measure(data, self.sample_rate, level);
+        }
+    }
+
+    #[inline(always)]
+    pub fn measure_output(&mut self, data: &[f32]) {
+        self.output
+            .measure(data, self.sample_rate, &self.meters.output);
+    }
+
+    #[inline(always)]
+    pub fn set_processors_count(&mut self, processors_count: usize) {
+        self.meters
+            .processors_count
+            .store(processors_count, Ordering::Relaxed);
+    }
+}
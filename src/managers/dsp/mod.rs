@@ -1,9 +1,17 @@
-use crate::managers::dsp::error::{Error, TransformProcessorError};
+use crate::managers::dsp::adaptive_resampler::AdaptiveResampler;
+use crate::managers::dsp::analysis_tap::{
+    get_processor_stage, AnalysisSource, AnalysisTap, INPUT_STAGE, OUTPUT_STAGE,
+};
+use crate::managers::dsp::error::{Error, TempoError, TransformProcessorError};
+use crate::managers::dsp::fader::{Fader, FaderProbe};
+use crate::managers::dsp::meters::{Meters, MetersProbe};
 use crate::managers::dsp::models::{
-    Device, Driver, ProcessorInfo, ProcessorParameters, ProcessorSettings,
+    AudioConfig, Device, Driver, ExpressionAssignment, ProcessorInfo, ProcessorParameters,
+    ProcessorSettings, ProcessorType, TempoSource,
 };
-use crate::processors::frontline::BoxedProcessor;
-use crate::settings::Settings;
+use crate::managers::dsp::transport::{TapTempo, Transport};
+use crate::processors::frontline::models::{NoiseProfile, Parameter, ParameterValue};
+use crate::processors::frontline::{create_processor_from_type, BoxedProcessor};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{default_host, host_from_id, Stream};
 use ringbuf::consumer::Consumer;
@@ -11,31 +19,106 @@ use ringbuf::producer::Producer;
 use ringbuf::traits::Split;
 use ringbuf::HeapRb;
 use std::ops::Add;
-use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Instant, SystemTime};
 
+pub mod adaptive_resampler;
+pub mod analysis_tap;
+pub mod audio_config;
 pub mod error;
+pub mod fader;
+pub mod meters;
 pub mod models;
+pub mod transport;
 
 type ProcessorsVector = Arc<Mutex<Vec<BoxedProcessor>>>;
 type ProcessorSettingsVector = Arc<Mutex<Vec<ProcessorSettings>>>;
 type CpuUsage = Arc<Mutex<Option<f32>>>;
 
+static ANALYSIS_BUFFER_SIZE: usize = 1 << 16;
+
+/// The cpal streams, which are rebuilt whenever the audio config changes
+struct Streams {
+    input: Stream,
+    output: Stream,
+}
+
+/// Stops the engine without holding the lock while the output fades out
+pub async fn stop_gracefully(dsp_manager: &Mutex<DSPManager>) -> Result<(), Error> {
+    let fader = dsp_manager.lock().unwrap().begin_stop();
+    if let Some(fader) = fader {
+        fader.wait_until_silent().await;
+    }
+    dsp_manager.lock().unwrap().finish_stop()
+}
+
 pub struct DSPManager {
+    analysis_sample_rate: Arc<AtomicU32>,
+    analysis_source: Option<AnalysisSource>,
+    analysis_tap: Arc<Mutex<AnalysisTap>>,
+    audio_config: AudioConfig,
     pub buffer_size: usize,
     cpu_usage: CpuUsage,
-    input_stream: Stream,
-    output_stream: Stream,
+    expression_assignments: Vec<ExpressionAssignment>,
+    expression_position: f32,
+    fader: Arc<Fader>,
+    is_running: bool,
+    meters: Arc<Meters>,
     processors: ProcessorsVector,
     processors_settings: ProcessorSettingsVector,
+    // Known on insertion, so that the expression pedal doesn't ask every processor under the lock
+    processor_types: Vec<ProcessorType>,
     pub sample_rate: u32,
+    streams: Option<Streams>,
+    tap_tempo: TapTempo,
+    transport: Arc<Transport>,
 }
 
 unsafe impl Send for DSPManager {}
 
 impl DSPManager {
-    pub fn new(settings: &Settings) -> Result<Self, Error> {
-        let host = match &settings.driver {
+    pub fn new(audio_config: &AudioConfig) -> Result<Self, Error> {
+        let (analysis_producer, analysis_consumer) =
+            HeapRb::<f32>::new(ANALYSIS_BUFFER_SIZE).split();
+        let analysis_tap_stage = Arc::new(AtomicUsize::new(OUTPUT_STAGE));
+        let analysis_sample_rate = Arc::new(AtomicU32::new(audio_config.output_sample_rate));
+
+        let mut dsp_manager = Self {
+            analysis_sample_rate: analysis_sample_rate.clone(),
+            analysis_source: Some(AnalysisSource {
+                consumer: analysis_consumer,
+                sample_rate: analysis_sample_rate,
+                tap_stage: analysis_tap_stage.clone(),
+            }),
+            analysis_tap: Arc::new(Mutex::new(AnalysisTap {
+                producer: analysis_producer,
+                tap_stage: analysis_tap_stage,
+            })),
+            audio_config: audio_config.clone(),
+            buffer_size: audio_config.buffer_size,
+            cpu_usage: Arc::new(Mutex::new(None)),
+            expression_assignments: vec![],
+            expression_position: 1.0,
+            fader: Arc::new(Fader::new()),
+            is_running: false,
+            meters: Arc::new(Meters::new()),
+            processors: Arc::new(Mutex::new(vec![])),
+            processors_settings: Arc::new(Mutex::new(vec![])),
+            processor_types: vec![],
+            sample_rate: audio_config.output_sample_rate,
+            streams: None,
+            tap_tempo: TapTempo::new(),
+            transport: Arc::new(Transport::new()),
+        };
+        dsp_manager.open_streams(audio_config)?;
+        Ok(dsp_manager)
+    }
+
+    /// Builds paused streams for `audio_config`. The chain, the meters, the analyser and the
+    /// transport are shared with the previous streams, if any.
+    fn open_streams(&mut self, audio_config: &AudioConfig) -> Result<(), Error> {
+        let host = match &audio_config.driver {
             Driver::Default => Ok(default_host()),
             other => host_from_id(other.host_id()),
         }?;
@@ -43,26 +126,24 @@ impl DSPManager {
         let input_device = get_device(
             host.default_input_device(),
             host.input_devices()?.collect(),
-            &settings.input_device,
+            &audio_config.input_device,
         )?;
         let output_device = get_device(
             host.default_output_device(),
             host.output_devices()?.collect(),
-            &settings.output_device,
+            &audio_config.output_device,
         )?;
 
-        let input_config = settings.audio_input_stream_config();
-        let output_config = settings.audio_output_stream_config();
-        let total_buffer_size = settings.max_latency_in_samples + settings.buffer_size;
+        let input_config = audio_config.input_stream_config();
+        let output_config = audio_config.output_stream_config();
+        let total_buffer_size = audio_config.max_latency_in_samples + audio_config.buffer_size;
         let buffer = HeapRb::<f32>::new(total_buffer_size);
         let (mut producer, mut consumer) = buffer.split();
         let mut input_samples_fell_behind = false;
         let mut output_samples_fell_behind = false;
 
-        let processors: ProcessorsVector = Arc::new(Mutex::new(vec![]));
-        let consumed_processors = processors.clone();
-        let processors_settings: ProcessorSettingsVector = Arc::new(Mutex::new(vec![]));
-        let consumed_processors_settings = processors_settings.clone();
+        let consumed_processors = self.processors.clone();
+        let consumed_processors_settings = self.processors_settings.clone();
 
         let input_data_fn = move |data: &[f32], _: &cpal::InputCallbackInfo| {
             for &sample in data {
@@ -74,40 +155,70 @@ impl DSPManager {
         };
 
         let mut cpu_usage_monitor = SystemTime::now();
-        let cpu_usage: CpuUsage = Arc::new(Mutex::new(None));
-        let cpu_usage_producer = cpu_usage.clone();
-        let mut data_vector = vec![0.0; settings.buffer_size];
+        let cpu_usage_producer = self.cpu_usage.clone();
+        let mut data_vector = vec![0.0; audio_config.buffer_size];
+        let mut meters_probe =
+            MetersProbe::new(self.meters.clone(), audio_config.output_sample_rate);
+        let analysis_tap = self.analysis_tap.clone();
+        let consumed_transport = self.transport.clone();
+        let mut fader_probe = FaderProbe::new(self.fader.clone(), audio_config.output_sample_rate);
+        // A single device runs both streams on the same clock, samples can be moved as they are
+        let mut adaptive_resampler = (audio_config.input_sample_rate
+            != audio_config.output_sample_rate
+            || audio_config.input_device != audio_config.output_device)
+            .then(|| {
+                AdaptiveResampler::new(
+                    audio_config.input_sample_rate,
+                    audio_config.output_sample_rate,
+                    audio_config.buffer_size,
+                    audio_config.max_latency_in_samples,
+                )
+            });
 
         let output_data_fn = move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
             // This truncates or extends the buffer without reallocating memory
             data_vector.resize(data.len(), 0.0);
-            for sample in data_vector.iter_mut() {
-                *sample = match consumer.try_pop() {
-                    Some(extracted_sample) => extracted_sample,
-                    None => {
-                        if !input_samples_fell_behind {
-                            input_samples_fell_behind = true;
-                            tracing::error!("input stream fell behind: try increasing latency");
-                        }
-                        0.0
-                    }
-                };
+            let is_input_available = match adaptive_resampler.as_mut() {
+                Some(adaptive_resampler) => {
+                    adaptive_resampler.process(&mut consumer, &mut data_vector)
+                }
+                None => {
+                    let read_count = consumer.pop_slice(&mut data_vector);
+                    data_vector[read_count..].fill(0.0);
+                    read_count == data_vector.len()
+                }
+            };
+            if !is_input_available && !input_samples_fell_behind {
+                input_samples_fell_behind = true;
+                tracing::error!("input stream fell behind: try increasing latency");
             }
 
             let cpu_idle_time = cpu_usage_monitor.elapsed();
             cpu_usage_monitor = SystemTime::now();
+            // The tap is only locked while the streams are rebuilt, the analyser just misses a block
+            let mut analysis_tap = analysis_tap.try_lock().ok();
+            meters_probe.measure_input(&data_vector);
+            capture(&mut analysis_tap, INPUT_STAGE, &data_vector);
+            let context = consumed_transport.get_context();
             if let Ok(unwrapped_processors_settings) = consumed_processors_settings.try_lock() {
                 if let Ok(mut unwrapped_processors) = consumed_processors.try_lock() {
-                    for (processor, unwrapped_processor_settings) in unwrapped_processors
+                    meters_probe.set_processors_count(unwrapped_processors.len());
+                    for (index, (processor, unwrapped_processor_settings)) in unwrapped_processors
                         .iter_mut()
                         .zip(unwrapped_processors_settings.iter())
+                        .enumerate()
                     {
                         if !unwrapped_processor_settings.bypassed {
-                            processor.process(&mut data_vector);
+                            processor.process(&mut data_vector, &context);
                         }
+                        meters_probe.measure_processor(index, &data_vector);
+                        capture(&mut analysis_tap, get_processor_stage(index), &data_vector);
                     }
                 }
             }
+            meters_probe.measure_output(&data_vector);
+            capture(&mut analysis_tap, OUTPUT_STAGE, &data_vector);
+            fader_probe.apply(&mut data_vector);
 
             for (processed_sample, output_sample) in data_vector.iter_mut().zip(data) {
                 *output_sample = *processed_sample;
@@ -142,9 +253,9 @@ impl DSPManager {
         output_stream.pause()?;
 
         tracing::info!("audio host selected: {}", host.id().name());
-        tracing::info!("buffer size: {}", settings.buffer_size);
-        tracing::info!("input sample rate: {}", settings.input_sample_rate);
-        tracing::info!("output sample rate: {}", settings.output_sample_rate);
+        tracing::info!("buffer size: {}", audio_config.buffer_size);
+        tracing::info!("input sample rate: {}", audio_config.input_sample_rate);
+        tracing::info!("output sample rate: {}", audio_config.output_sample_rate);
         tracing::info!(
             "input audio device selected: {} with {} channels",
             input_device.name()?,
@@ -156,28 +267,182 @@ impl DSPManager {
             output_config.channels
         );
 
-        Ok(Self {
-            buffer_size: settings.buffer_size,
-            cpu_usage,
-            input_stream,
-            output_stream,
-            processors,
-            processors_settings,
-            sample_rate: settings.input_sample_rate,
-        })
+        self.streams = Some(Streams {
+            input: input_stream,
+            output: output_stream,
+        });
+        self.audio_config = audio_config.clone();
+        self.buffer_size = audio_config.buffer_size;
+        // Processors run in the output callback, after the resampler
+        self.sample_rate = audio_config.output_sample_rate;
+        self.analysis_sample_rate
+            .store(audio_config.output_sample_rate, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Plays the streams, fading the output in
+    pub fn start(&mut self) -> Result<(), Error> {
+        let streams = self.streams.as_ref().ok_or(Error::StreamsClosed)?;
+        self.fader.open();
+        streams.input.play()?;
+        streams.output.play()?;
+        self.is_running = true;
+        Ok(())
     }
 
-    pub fn start(&self) -> Result<(), Error> {
-        self.input_stream.play()?;
-        self.output_stream.play()?;
+    /// Fades the output to silence, then pauses the streams. This blocks during the fade, so
+    /// async code goes through `stop_gracefully` instead.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        if let Some(fader) = self.begin_stop() {
+            fader.wait_until_silent_blocking();
+        }
+        self.finish_stop()
+    }
+
+    /// Starts fading the output out. Returns the fader to wait on before `finish_stop`, or
+    /// `None` when the engine is not running.
+    pub fn begin_stop(&self) -> Option<Arc<Fader>> {
+        if !self.is_running || self.streams.is_none() {
+            return None;
+        }
+        self.fader.close();
+        Some(self.fader.clone())
+    }
+
+    /// Pauses the streams, unless the engine was started again during the fade
+    pub fn finish_stop(&mut self) -> Result<(), Error> {
+        let Some(streams) = self.streams.as_ref().filter(|_| self.is_running) else {
+            return Ok(());
+        };
+        if self.fader.is_open() {
+            return Ok(());
+        }
+        streams.output.pause()?;
+        streams.input.pause()?;
+        self.is_running = false;
         Ok(())
     }
 
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    pub fn get_audio_config(&self) -> AudioConfig {
+        self.audio_config.clone()
+    }
+
+    /// Rebuilds the streams with a new config. The chain is re-instantiated at the new sample
+    /// rate and buffer size, keeping its parameters. If the new streams cannot be built, the
+    /// previous config is restored.
+    pub fn reconfigure(&mut self, audio_config: &AudioConfig) -> Result<(), Error> {
+        let was_running = self.is_running;
+        let previous_audio_config = self.audio_config.clone();
+        self.stop()?;
+        // Some drivers open devices exclusively, so the old streams go away first
+        self.streams = None;
+
+        let result = self.open_streams(audio_config);
+        if let Err(error) = &result {
+            tracing::error!(
+                "cannot build the audio streams, restoring the previous config: {:?}",
+                error
+            );
+            if let Err(error) = self.open_streams(&previous_audio_config) {
+                tracing::error!("cannot restore the previous audio config: {:?}", error);
+            }
+        }
+
+        if self.sample_rate != previous_audio_config.output_sample_rate
+            || self.buffer_size != previous_audio_config.buffer_size
+        {
+            self.reinstantiate_processors();
+        }
+        if was_running && self.streams.is_some() {
+            self.start()?;
+        }
+        result
+    }
+
+    /// Processors size their state on the sample rate and the buffer size, so they are created
+    /// again with the same parameters and settings. Learned noise profiles are carried over,
+    /// anything else running, like an LFO phase, starts over.
+    fn reinstantiate_processors(&mut self) {
+        let processors_info = self.get_processors_info();
+        let noise_profiles: Vec<Option<NoiseProfile>> = self
+            .processors
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|processor| processor.get_noise_profile())
+            .collect();
+        let processors = processors_info
+            .iter()
+            .zip(noise_profiles)
+            .map(|(processor_info, noise_profile)| {
+                let mut processor = self.create_configured_processor(processor_info);
+                if let Some(noise_profile) = noise_profile {
+                    processor.set_noise_profile(&noise_profile);
+                }
+                processor
+            })
+            .collect();
+        *self.processors.lock().unwrap() = processors;
+    }
+
     pub fn get_audio_cpu_usage(&self) -> Option<f32> {
         self.cpu_usage.try_lock().ok().and_then(|value| *value)
     }
 
+    pub fn get_meters(&self) -> Arc<Meters> {
+        self.meters.clone()
+    }
+
+    pub fn get_transport(&self) -> Arc<Transport> {
+        self.transport.clone()
+    }
+
+    pub fn get_tempo(&self) -> f32 {
+        self.transport.get_tempo()
+    }
+
+    pub fn get_tempo_source(&self) -> TempoSource {
+        self.transport.get_source()
+    }
+
+    pub fn set_tempo_source(&mut self, source: TempoSource) {
+        self.transport.set_source(source);
+    }
+
+    pub fn set_tempo(&mut self, tempo: f32) -> Result<f32, TempoError> {
+        match self.transport.get_source() {
+            TempoSource::Internal => Ok(self.transport.set_tempo(tempo)),
+            TempoSource::MidiClock => Err(TempoError::SlavedToMidiClock),
+        }
+    }
+
+    /// Registers a tap and returns the new tempo, if enough taps were collected
+    pub fn tap_tempo(&mut self) -> Result<Option<f32>, TempoError> {
+        if self.transport.get_source() == TempoSource::MidiClock {
+            return Err(TempoError::SlavedToMidiClock);
+        }
+        Ok(self
+            .tap_tempo
+            .tap(Instant::now())
+            .map(|tempo| self.transport.set_tempo(tempo)))
+    }
+
+    /// Hands the tapped audio over to the analyser. It can only be taken once.
+    pub fn take_analysis_source(&mut self) -> Option<AnalysisSource> {
+        self.analysis_source.take()
+    }
+
     pub fn add_processor(&mut self, index: usize, processor: BoxedProcessor) {
+        for assignment in self.expression_assignments.iter_mut() {
+            if assignment.processor_index >= index {
+                assignment.processor_index += 1;
+            }
+        }
+        self.processor_types.insert(index, processor.get_type());
         self.processors.lock().unwrap().insert(index, processor);
         self.processors_settings
             .lock()
@@ -220,31 +485,273 @@ impl DSPManager {
     }
 
     pub fn extract_processor(&mut self, index: usize) -> BoxedProcessor {
+        self.expression_assignments
+            .retain(|assignment| assignment.processor_index != index);
+        for assignment in self.expression_assignments.iter_mut() {
+            if assignment.processor_index > index {
+                assignment.processor_index -= 1;
+            }
+        }
+        self.processor_types.remove(index);
         self.processors_settings.lock().unwrap().remove(index);
         self.processors.lock().unwrap().remove(index)
     }
 
-    pub fn clear_all_processors(&self) {
+    pub fn clear_all_processors(&mut self) {
+        self.expression_assignments.clear();
+        self.processor_types.clear();
         self.processors_settings.lock().unwrap().clear();
         self.processors.lock().unwrap().clear();
     }
 
+    pub fn get_processors_count(&self) -> usize {
+        self.processors.lock().unwrap().len()
+    }
+
+    pub fn create_processor(
+        &mut self,
+        index: usize,
+        processor_type: &ProcessorType,
+    ) -> Result<ProcessorInfo, TransformProcessorError> {
+        if index > self.get_processors_count() {
+            return Err(TransformProcessorError::NotFound);
+        }
+        let processor =
+            create_processor_from_type(processor_type, &self.sample_rate, &self.buffer_size);
+        self.add_processor(index, processor);
+        self.get_processor_info(index)
+            .ok_or(TransformProcessorError::NotFound)
+    }
+
+    pub fn swap_processor(
+        &mut self,
+        index: usize,
+        processor_type: &ProcessorType,
+    ) -> Result<ProcessorInfo, TransformProcessorError> {
+        self.delete_processor(index)?;
+        self.create_processor(index, processor_type)
+    }
+
+    pub fn delete_processor(&mut self, index: usize) -> Result<(), TransformProcessorError> {
+        if index >= self.get_processors_count() {
+            return Err(TransformProcessorError::NotFound);
+        }
+        self.extract_processor(index);
+        Ok(())
+    }
+
+    /// Moves the processor at `index` so that it ends up right before the processor currently
+    /// at `destination_index`, keeping its settings and expression assignments.
+    pub fn move_processor(
+        &mut self,
+        index: usize,
+        destination_index: usize,
+    ) -> Result<(), TransformProcessorError> {
+        let processors_count = self.get_processors_count();
+        if index >= processors_count || destination_index > processors_count {
+            return Err(TransformProcessorError::NotFound);
+        }
+
+        let settings = self.processors_settings.lock().unwrap()[index].clone();
+        let mut assignments: Vec<ExpressionAssignment> = self
+            .expression_assignments
+            .iter()
+            .filter(|assignment| assignment.processor_index == index)
+            .cloned()
+            .collect();
+        let processor = self.extract_processor(index);
+
+        let destination_index = if destination_index <= index {
+            destination_index
+        } else {
+            destination_index - 1
+        };
+        self.add_processor(destination_index, processor);
+        self.processors_settings.lock().unwrap()[destination_index] = settings;
+        for assignment in assignments.iter_mut() {
+            assignment.processor_index = destination_index;
+        }
+        self.expression_assignments.append(&mut assignments);
+        Ok(())
+    }
+
+    /// Replaces the whole chain, e.g. when a preset is loaded
+    pub fn load_processors(&mut self, processors_info: &[ProcessorInfo]) {
+        self.clear_all_processors();
+        for (index, processor_info) in processors_info.iter().enumerate() {
+            let _ = self.insert_processor(index, processor_info);
+        }
+    }
+
+    /// Creates a processor with the given parameters and settings at `index`
+    pub fn insert_processor(
+        &mut self,
+        index: usize,
+        processor_info: &ProcessorInfo,
+    ) -> Result<ProcessorInfo, TransformProcessorError> {
+        if index > self.get_processors_count() {
+            return Err(TransformProcessorError::NotFound);
+        }
+        let processor = self.create_configured_processor(processor_info);
+        self.add_processor(index, processor);
+        self.transform_processor_settings(index, |settings| {
+            settings.bypassed = processor_info.settings.bypassed;
+        })?;
+        self.get_processor_info(index)
+            .ok_or(TransformProcessorError::NotFound)
+    }
+
+    fn create_configured_processor(&self, processor_info: &ProcessorInfo) -> BoxedProcessor {
+        let mut processor = create_processor_from_type(
+            &processor_info.processor_type,
+            &self.sample_rate,
+            &self.buffer_size,
+        );
+        for (parameter, value) in processor_info.parameters.numeric.iter() {
+            processor.set_parameter(*parameter, ParameterValue::Numeric(*value));
+        }
+        for (parameter, value) in processor_info.parameters.string.iter() {
+            processor.set_parameter(*parameter, ParameterValue::String(value.clone()));
+        }
+        processor
+    }
+
+    /// Overrides parameters and bypass states of the current chain in place, e.g. when a scene is
+    /// selected. Processors whose type doesn't match are left untouched.
+    pub fn apply_processors_overrides(&mut self, processors_info: &[ProcessorInfo]) {
+        let mut processors_settings = self.processors_settings.lock().unwrap();
+        let mut processors = self.processors.lock().unwrap();
+        for (((processor_info, settings), processor), processor_type) in processors_info
+            .iter()
+            .zip(processors_settings.iter_mut())
+            .zip(processors.iter_mut())
+            .zip(&self.processor_types)
+        {
+            if *processor_type != processor_info.processor_type {
+                continue;
+            }
+            settings.bypassed = processor_info.settings.bypassed;
+            let values = processor_info
+                .parameters
+                .numeric
+                .iter()
+                .map(|(parameter, value)| (*parameter, ParameterValue::Numeric(*value)))
+                .chain(
+                    processor_info
+                        .parameters
+                        .string
+                        .iter()
+                        .map(|(parameter, value)| {
+                            (*parameter, ParameterValue::String(value.clone()))
+                        }),
+                );
+            for (parameter, value) in values {
+                // Setting a file path reloads the file, so unchanged values are skipped
+                if processor.get_parameter(parameter) != Some(value.clone()) {
+                    processor.set_parameter(parameter, value);
+                }
+            }
+        }
+    }
+
+    pub fn get_processor_info(&self, index: usize) -> Option<ProcessorInfo> {
+        let processors_settings = self.processors_settings.lock().unwrap();
+        let processors = self.processors.lock().unwrap();
+        let settings = processors_settings.get(index)?;
+        let processor = processors.get(index)?;
+        let processor_type = self.processor_types.get(index)?;
+        Some(make_processor_info(settings, processor, processor_type))
+    }
+
     pub fn get_processors_info(&self) -> Vec<ProcessorInfo> {
         self.processors_settings
             .lock()
             .unwrap()
             .iter()
             .zip(self.processors.lock().unwrap().iter())
-            .map(|(settings, processor)| ProcessorInfo {
-                settings: (*settings).clone(),
-                parameters: ProcessorParameters {
-                    numeric: processor.get_numeric_parameters(),
-                    string: processor.get_string_parameters(),
-                },
-                processor_type: processor.get_type(),
+            .zip(&self.processor_types)
+            .map(|((settings, processor), processor_type)| {
+                make_processor_info(settings, processor, processor_type)
             })
             .collect()
     }
+
+    pub fn get_expression_position(&self) -> f32 {
+        self.expression_position
+    }
+
+    pub fn get_expression_assignments(&self) -> Vec<ExpressionAssignment> {
+        self.expression_assignments.clone()
+    }
+
+    pub fn add_expression_assignment(
+        &mut self,
+        assignment: ExpressionAssignment,
+    ) -> Result<(), TransformProcessorError> {
+        if assignment.processor_index >= self.processors.lock().unwrap().len() {
+            return Err(TransformProcessorError::NotFound);
+        }
+        self.expression_assignments.push(assignment);
+        Ok(())
+    }
+
+    /// Replaces every assignment, e.g. when a preset is loaded. Those pointing past the end of
+    /// the chain are dropped.
+    pub fn set_expression_assignments(&mut self, assignments: &[ExpressionAssignment]) {
+        let processors_count = self.get_processors_count();
+        self.expression_assignments = assignments
+            .iter()
+            .filter(|assignment| assignment.processor_index < processors_count)
+            .cloned()
+            .collect();
+    }
+
+    pub fn remove_expression_assignment(
+        &mut self,
+        assignment_index: usize,
+    ) -> Result<(), TransformProcessorError> {
+        if assignment_index >= self.expression_assignments.len() {
+            return Err(TransformProcessorError::NotFound);
+        }
+        self.expression_assignments.remove(assignment_index);
+        Ok(())
+    }
+
+    /// Moves every volume pedal in the chain to `position` and sweeps each assigned parameter
+    /// between its minimum and maximum accordingly.
+    pub fn set_expression_position(&mut self, position: f32) {
+        let position = position.clamp(0.0, 1.0);
+        self.expression_position = position;
+
+        let mut processors = self.processors.lock().unwrap();
+        for (processor, processor_type) in processors.iter_mut().zip(&self.processor_types) {
+            if *processor_type == ProcessorType::VolumePedal {
+                processor.set_parameter(Parameter::Position, ParameterValue::Numeric(position));
+            }
+        }
+        for assignment in self.expression_assignments.iter() {
+            if let Some(processor) = processors.get_mut(assignment.processor_index) {
+                let value =
+                    assignment.minimum + (assignment.maximum - assignment.minimum) * position;
+                processor.set_parameter(assignment.parameter, ParameterValue::Numeric(value));
+            }
+        }
+    }
+}
+
+fn make_processor_info(
+    settings: &ProcessorSettings,
+    processor: &BoxedProcessor,
+    processor_type: &ProcessorType,
+) -> ProcessorInfo {
+    ProcessorInfo {
+        settings: settings.clone(),
+        parameters: ProcessorParameters {
+            numeric: processor.get_numeric_parameters(),
+            string: processor.get_string_parameters(),
+        },
+        processor_type: processor_type.clone(),
+    }
 }
 
 fn get_device(
@@ -259,14 +766,19 @@ fn get_device(
             .find(|candidate_device| {
                 candidate_device
                     .name()
-                    .map_or(false, |candidate_device_name| {
-                        candidate_device_name == *device_name
-                    })
+                    .is_ok_and(|candidate_device_name| candidate_device_name == *device_name)
             })
             .ok_or(Error::NamedDeviceNotFound),
     }
 }
 
+#[inline(always)]
+fn capture(analysis_tap: &mut Option<MutexGuard<AnalysisTap>>, stage: usize, data: &[f32]) {
+    if let Some(analysis_tap) = analysis_tap {
+        analysis_tap.capture(stage, data);
+    }
+}
+
 fn handle_error(error: cpal::StreamError) {
     tracing::error!("audio processing error: {}", error);
 }
@@ -1,6 +1,6 @@
 use crate::processors::frontline::models::Parameter;
 use clap::ValueEnum;
-use cpal::HostId;
+use cpal::{BufferSize, HostId, SampleRate, StreamConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -11,7 +11,8 @@ pub struct ProcessorSettings {
     pub bypassed: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
 pub enum Device {
     Default,
     Named(String),
@@ -21,15 +22,31 @@ impl FromStr for Device {
     type Err = String;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(if input == "default" {
+        Ok(Device::from(input.to_string()))
+    }
+}
+
+impl From<String> for Device {
+    fn from(value: String) -> Self {
+        if value == "default" {
             Device::Default
         } else {
-            Device::Named(input.to_string())
-        })
+            Device::Named(value)
+        }
     }
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+impl From<Device> for String {
+    fn from(value: Device) -> Self {
+        match value {
+            Device::Default => "default".to_string(),
+            Device::Named(name) => name,
+        }
+    }
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub enum Driver {
     Default,
     #[cfg(target_os = "windows")]
@@ -55,7 +72,7 @@ impl Driver {
     }
 }
 
-#[derive(EnumString, AsRefStr, Debug, Serialize, Deserialize)]
+#[derive(EnumString, AsRefStr, Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ProcessorType {
     SimpleDistortion,
@@ -63,6 +80,11 @@ pub enum ProcessorType {
     Clone,
     CleanBooster,
     Ocd,
+    BitCrusher,
+    RingModulator,
+    VolumePedal,
+    Rotary,
+    HumRemover,
 }
 
 impl From<String> for ProcessorType {
@@ -74,15 +96,109 @@ impl From<String> for ProcessorType {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ProcessorParameters {
     pub numeric: HashMap<Parameter, f32>,
     pub string: HashMap<Parameter, String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MeterReading {
+    pub peak: f32,
+    pub rms: f32,
+    pub clipping: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MetersReadings {
+    pub input: MeterReading,
+    pub output: MeterReading,
+    pub processors: Vec<MeterReading>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExpressionAssignment {
+    pub processor_index: usize,
+    pub parameter: Parameter,
+    pub minimum: f32,
+    pub maximum: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TempoSource {
+    Internal,
+    MidiClock,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ProcessorInfo {
     pub processor_type: ProcessorType,
     pub settings: ProcessorSettings,
     pub parameters: ProcessorParameters,
 }
+
+/// Everything the audio streams are built from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AudioConfig {
+    pub driver: Driver,
+    pub input_device: Device,
+    pub output_device: Device,
+    pub input_channels: u16,
+    pub output_channels: u16,
+    pub input_sample_rate: u32,
+    pub output_sample_rate: u32,
+    pub buffer_size: usize,
+    pub max_latency_in_samples: usize,
+}
+
+impl AudioConfig {
+    pub fn input_stream_config(&self) -> StreamConfig {
+        StreamConfig {
+            buffer_size: BufferSize::Fixed(self.buffer_size as u32),
+            channels: self.input_channels,
+            sample_rate: SampleRate(self.input_sample_rate),
+        }
+    }
+
+    pub fn output_stream_config(&self) -> StreamConfig {
+        StreamConfig {
+            buffer_size: BufferSize::Fixed(self.buffer_size as u32),
+            channels: self.output_channels,
+            sample_rate: SampleRate(self.output_sample_rate),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.input_channels > 0
+            && self.output_channels > 0
+            && self.input_sample_rate > 0
+            && self.output_sample_rate > 0
+            && self.buffer_size > 0
+            && self.max_latency_in_samples > 0
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AudioHost {
+    pub driver: Driver,
+    pub input_devices: Vec<AudioDevice>,
+    pub output_devices: Vec<AudioDevice>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AudioDevice {
+    pub name: String,
+    pub is_default: bool,
+    pub configs: Vec<SupportedAudioConfig>,
+}
+
+/// Buffer sizes are `None` when the driver doesn't report them
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SupportedAudioConfig {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+    pub min_buffer_size: Option<u32>,
+    pub max_buffer_size: Option<u32>,
+}
@@ -0,0 +1,93 @@
+use crate::managers::dsp::models::TempoSource;
+use crate::processors::ProcessingContext;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+pub static DEFAULT_TEMPO: f32 = 120.0;
+static MIN_TEMPO: f32 = 20.0;
+static MAX_TEMPO: f32 = 300.0;
+static MAX_TAPS_COUNT: usize = 5;
+// A longer pause means a new tempo is being tapped
+static MAX_TAP_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Global tempo, written by the control side and read by the audio thread on every block.
+pub struct Transport {
+    tempo: AtomicU32,
+    is_slaved_to_midi_clock: AtomicBool,
+}
+
+impl Transport {
+    pub fn new() -> Self {
+        Self {
+            tempo: AtomicU32::new(DEFAULT_TEMPO.to_bits()),
+            is_slaved_to_midi_clock: AtomicBool::new(false),
+        }
+    }
+
+    pub fn get_tempo(&self) -> f32 {
+        f32::from_bits(self.tempo.load(Ordering::Relaxed))
+    }
+
+    pub fn set_tempo(&self, tempo: f32) -> f32 {
+        let tempo = tempo.clamp(MIN_TEMPO, MAX_TEMPO);
+        self.tempo.store(tempo.to_bits(), Ordering::Relaxed);
+        tempo
+    }
+
+    pub fn get_source(&self) -> TempoSource {
+        if self.is_slaved_to_midi_clock.load(Ordering::Relaxed) {
+            TempoSource::MidiClock
+        } else {
+            TempoSource::Internal
+        }
+    }
+
+    pub fn set_source(&self, source: TempoSource) {
+        self.is_slaved_to_midi_clock
+            .store(source == TempoSource::MidiClock, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn get_context(&self) -> ProcessingContext {
+        ProcessingContext {
+            tempo: self.get_tempo(),
+        }
+    }
+}
+
+pub struct TapTempo {
+    taps: VecDeque<Instant>,
+}
+
+impl TapTempo {
+    pub fn new() -> Self {
+        Self {
+            taps: VecDeque::with_capacity(MAX_TAPS_COUNT),
+        }
+    }
+
+    /// Returns the average tempo of the recent taps, once there are at least two
+    pub fn tap(&mut self, now: Instant) -> Option<f32> {
+        if self
+            .taps
+            .back()
+            .is_some_and(|last_tap| now.duration_since(*last_tap) > MAX_TAP_INTERVAL)
+        {
+            self.taps.clear();
+        }
+        if self.taps.len() == MAX_TAPS_COUNT {
+            self.taps.pop_front();
+        }
+        self.taps.push_back(now);
+
+        let first_tap = self.taps.front()?;
+        let intervals_count = self.taps.len() - 1;
+        if intervals_count == 0 {
+            return None;
+        }
+        let average_interval =
+            now.duration_since(*first_tap).as_secs_f32() / intervals_count as f32;
+        Some(60.0 / average_interval)
+    }
+}
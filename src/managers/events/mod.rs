@@ -0,0 +1,28 @@
+use crate::managers::events::models::StateEvent;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
+
+pub mod models;
+
+static EVENTS_CHANNEL_CAPACITY: usize = 256;
+
+/// Fans out every change of the rig state to all the connected clients.
+#[derive(Clone)]
+pub struct EventsManager {
+    sender: Sender<StateEvent>,
+}
+
+impl EventsManager {
+    pub fn new() -> Self {
+        let (sender, _) = channel(EVENTS_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: StateEvent) {
+        // Sending only fails when nobody is listening, which is fine
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> Receiver<StateEvent> {
+        self.sender.subscribe()
+    }
+}
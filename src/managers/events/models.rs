@@ -0,0 +1,74 @@
+use crate::managers::dsp::models::{AudioConfig, ProcessorInfo, TempoSource};
+use crate::managers::midi::models::MidiMapping;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StateEvent {
+    ProcessorCreated {
+        index: usize,
+        processor: ProcessorInfo,
+    },
+    ProcessorSwapped {
+        index: usize,
+        processor: ProcessorInfo,
+    },
+    ProcessorDeleted {
+        index: usize,
+    },
+    ProcessorMoved {
+        index: usize,
+        destination_index: usize,
+    },
+    ProcessorBypassed {
+        index: usize,
+        bypassed: bool,
+    },
+    ParameterChanged {
+        index: usize,
+        parameter: Parameter,
+        value: ParameterValue,
+    },
+    PresetLoaded {
+        id: Uuid,
+        processors: Vec<ProcessorInfo>,
+    },
+    ExpressionChanged {
+        position: f32,
+    },
+    InputGainChanged {
+        value: f32,
+    },
+    VolumeChanged {
+        value: f32,
+    },
+    MicBiasChanged {
+        on: bool,
+    },
+    MidiMappingsChanged {
+        mappings: Vec<MidiMapping>,
+    },
+    SceneSelected {
+        index: usize,
+        processors: Vec<ProcessorInfo>,
+    },
+    ScenesChanged {
+        names: Vec<String>,
+    },
+    SetlistPositionChanged {
+        setlist_id: Uuid,
+        position: usize,
+    },
+    TempoChanged {
+        tempo: f32,
+        source: TempoSource,
+    },
+    EngineStateChanged {
+        is_running: bool,
+    },
+    AudioConfigChanged {
+        config: AudioConfig,
+    },
+}
@@ -5,6 +5,7 @@ pub enum Error {
     Conversion,
     Generic(io::Error),
     HomeDirectoryNotFound,
+    InvalidPath,
     NotFound,
 }
 
@@ -2,8 +2,8 @@ pub mod error;
 use error::Error;
 
 use dirs::home_dir;
-use std::fs::read_dir;
-use std::path::PathBuf;
+use std::fs::{create_dir_all, read, read_dir, rename, write};
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Clone)]
 pub struct FileSystemManager {
@@ -26,6 +26,10 @@ impl FileSystemManager {
         Ok(Self { data_root_path })
     }
 
+    pub fn get_data_root_path(&self) -> &Path {
+        &self.data_root_path
+    }
+
     pub fn get_directory_names_in_directory(
         &self,
         relative_path: String,
@@ -47,6 +51,59 @@ impl FileSystemManager {
         Ok(directories)
     }
 
+    /// Path of a data file relative to the data root, if the file lives inside it
+    pub fn get_relative_path(&self, path: &str) -> Option<String> {
+        Path::new(path)
+            .strip_prefix(&self.data_root_path)
+            .ok()
+            .and_then(|relative_path| relative_path.to_str())
+            .map(|relative_path| relative_path.to_string())
+    }
+
+    pub fn get_absolute_path(&self, relative_path: &str) -> Result<String, Error> {
+        // Relative paths come from outside, they must not escape the data root
+        if !Path::new(relative_path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(Error::InvalidPath);
+        }
+        self.data_root_path
+            .join(relative_path)
+            .into_os_string()
+            .into_string()
+            .map_err(|_| Error::Conversion)
+    }
+
+    pub fn read_file(&self, relative_path: &str) -> Result<Vec<u8>, Error> {
+        let path = self.get_absolute_path(relative_path)?;
+        if !Path::new(&path).is_file() {
+            return Err(Error::NotFound);
+        }
+        Ok(read(path)?)
+    }
+
+    pub fn write_file(&self, relative_path: &str, content: &[u8]) -> Result<String, Error> {
+        let path = self.get_absolute_path(relative_path)?;
+        if let Some(parent) = Path::new(&path).parent() {
+            create_dir_all(parent)?;
+        }
+        write(&path, content)?;
+        Ok(path)
+    }
+
+    /// Writes next to the file and renames over it, so a crash never leaves it half written
+    pub fn write_file_atomically(
+        &self,
+        relative_path: &str,
+        content: &[u8],
+    ) -> Result<String, Error> {
+        let path = self.get_absolute_path(relative_path)?;
+        let temporary_path = self.write_file(&format!("{}.tmp", relative_path), content)?;
+        rename(temporary_path, &path)?;
+        Ok(path)
+    }
+
     pub fn get_file_names_in_directory(&self, relative_path: String) -> Result<Vec<String>, Error> {
         let folder_path = self.data_root_path.join(relative_path.clone());
         let mut directories: Vec<String> = vec![];
@@ -0,0 +1,7 @@
+#[derive(Debug)]
+pub enum Error {
+    NothingToRedo,
+    NothingToUndo,
+    /// The chain no longer matches the recorded edit, the history has been cleared
+    OutOfSync,
+}
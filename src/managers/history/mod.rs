@@ -0,0 +1,174 @@
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::history::error::Error;
+use crate::managers::history::models::{ChainEdit, HistoryStatus};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+pub mod error;
+pub mod models;
+
+#[derive(Default)]
+struct History {
+    undo_stack: VecDeque<ChainEdit>,
+    redo_stack: Vec<ChainEdit>,
+}
+
+/// Keeps the last edits of the live chain, so that they can be undone and redone.
+/// The history refers to chain positions, so it is cleared whenever the chain is replaced.
+#[derive(Clone)]
+pub struct HistoryManager {
+    dsp_manager: Arc<Mutex<DSPManager>>,
+    events_manager: EventsManager,
+    history: Arc<Mutex<History>>,
+    max_size: usize,
+}
+
+impl HistoryManager {
+    pub fn new(
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        max_size: usize,
+    ) -> Self {
+        Self {
+            dsp_manager,
+            events_manager,
+            history: Arc::new(Mutex::new(History::default())),
+            max_size,
+        }
+    }
+
+    /// Consecutive changes of the same parameter, e.g. a knob being turned, are kept as a single edit
+    pub fn record(&self, edit: ChainEdit) {
+        let mut history = self.history.lock().unwrap();
+        history.redo_stack.clear();
+        if let (
+            Some(ChainEdit::Parameter {
+                index: last_index,
+                parameter: last_parameter,
+                to: last_to,
+                ..
+            }),
+            ChainEdit::Parameter {
+                index,
+                parameter,
+                to,
+                ..
+            },
+        ) = (history.undo_stack.back_mut(), &edit)
+        {
+            if *last_index == *index && *last_parameter == *parameter {
+                *last_to = to.clone();
+                return;
+            }
+        }
+        history.undo_stack.push_back(edit);
+        while history.undo_stack.len() > self.max_size {
+            history.undo_stack.pop_front();
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut history = self.history.lock().unwrap();
+        history.undo_stack.clear();
+        history.redo_stack.clear();
+    }
+
+    pub fn get_status(&self) -> HistoryStatus {
+        let history = self.history.lock().unwrap();
+        HistoryStatus {
+            can_undo: !history.undo_stack.is_empty(),
+            can_redo: !history.redo_stack.is_empty(),
+        }
+    }
+
+    pub fn undo(&self) -> Result<HistoryStatus, Error> {
+        let edit = self
+            .history
+            .lock()
+            .unwrap()
+            .undo_stack
+            .pop_back()
+            .ok_or(Error::NothingToUndo)?;
+        self.apply(&edit.inverse())?;
+        self.history.lock().unwrap().redo_stack.push(edit);
+        Ok(self.get_status())
+    }
+
+    pub fn redo(&self) -> Result<HistoryStatus, Error> {
+        let edit = self
+            .history
+            .lock()
+            .unwrap()
+            .redo_stack
+            .pop()
+            .ok_or(Error::NothingToRedo)?;
+        self.apply(&edit)?;
+        self.history.lock().unwrap().undo_stack.push_back(edit);
+        Ok(self.get_status())
+    }
+
+    fn apply(&self, edit: &ChainEdit) -> Result<(), Error> {
+        let event = {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            match edit.clone() {
+                ChainEdit::Create { index, processor } => dsp_manager
+                    .insert_processor(index, &processor)
+                    .map(|processor| StateEvent::ProcessorCreated { index, processor }),
+                ChainEdit::Delete { index, .. } => dsp_manager
+                    .delete_processor(index)
+                    .map(|_| StateEvent::ProcessorDeleted { index }),
+                ChainEdit::Swap { index, to, .. } => dsp_manager
+                    .delete_processor(index)
+                    .and_then(|_| dsp_manager.insert_processor(index, &to))
+                    .map(|processor| StateEvent::ProcessorSwapped { index, processor }),
+                ChainEdit::Move {
+                    index,
+                    destination_index,
+                } => dsp_manager
+                    .move_processor(index, destination_index)
+                    .map(|_| StateEvent::ProcessorMoved {
+                        index,
+                        destination_index,
+                    }),
+                ChainEdit::Bypass { index, to, .. } => dsp_manager
+                    .transform_processor_settings(index, |settings| settings.bypassed = to)
+                    .map(|_| StateEvent::ProcessorBypassed {
+                        index,
+                        bypassed: to,
+                    }),
+                ChainEdit::Parameter {
+                    index,
+                    parameter,
+                    to,
+                    ..
+                } => {
+                    let mut value = to.clone();
+                    dsp_manager
+                        .transform_processor(index, |processor| {
+                            processor.set_parameter(parameter, to);
+                            if let Some(current_value) = processor.get_parameter(parameter) {
+                                value = current_value;
+                            }
+                        })
+                        .map(|_| StateEvent::ParameterChanged {
+                            index,
+                            parameter,
+                            value,
+                        })
+                }
+            }
+        };
+        match event {
+            Ok(event) => {
+                self.events_manager.publish(event);
+                Ok(())
+            }
+            Err(_) => {
+                self.clear();
+                Err(Error::OutOfSync)
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::managers::dsp::models::ProcessorInfo;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use serde::{Deserialize, Serialize};
+
+/// A reversible mutation of the live chain
+#[derive(Clone)]
+pub enum ChainEdit {
+    Create {
+        index: usize,
+        processor: ProcessorInfo,
+    },
+    Delete {
+        index: usize,
+        processor: ProcessorInfo,
+    },
+    Swap {
+        index: usize,
+        from: ProcessorInfo,
+        to: ProcessorInfo,
+    },
+    Move {
+        index: usize,
+        destination_index: usize,
+    },
+    Bypass {
+        index: usize,
+        from: bool,
+        to: bool,
+    },
+    Parameter {
+        index: usize,
+        parameter: Parameter,
+        from: ParameterValue,
+        to: ParameterValue,
+    },
+}
+
+impl ChainEdit {
+    pub fn inverse(&self) -> Self {
+        match self.clone() {
+            ChainEdit::Create { index, processor } => ChainEdit::Delete { index, processor },
+            ChainEdit::Delete { index, processor } => ChainEdit::Create { index, processor },
+            ChainEdit::Swap { index, from, to } => ChainEdit::Swap {
+                index,
+                from: to,
+                to: from,
+            },
+            ChainEdit::Move {
+                index,
+                destination_index,
+            } => {
+                // A processor moved forward lands right before the destination
+                let final_index = if destination_index <= index {
+                    destination_index
+                } else {
+                    destination_index - 1
+                };
+                ChainEdit::Move {
+                    index: final_index,
+                    destination_index: if index <= final_index {
+                        index
+                    } else {
+                        index + 1
+                    },
+                }
+            }
+            ChainEdit::Bypass { index, from, to } => ChainEdit::Bypass {
+                index,
+                from: to,
+                to: from,
+            },
+            ChainEdit::Parameter {
+                index,
+                parameter,
+                from,
+                to,
+            } => ChainEdit::Parameter {
+                index,
+                parameter,
+                from: to,
+                to: from,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct HistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+static PULSES_PER_QUARTER_NOTE: usize = 24;
+// Slower than 10 BPM means the clock was stopped and restarted
+static MAX_PULSE_INTERVAL_MICROSECONDS: u64 = 250_000;
+// Ignores the jitter of the pulses instead of flooding clients with tiny changes
+static TEMPO_CHANGE_THRESHOLD: f32 = 0.5;
+
+/// Estimates the tempo of an incoming MIDI clock, averaging the pulses of the last beat.
+pub struct MidiClock {
+    intervals: VecDeque<u64>,
+    last_timestamp: Option<u64>,
+    tempo: Option<f32>,
+}
+
+impl MidiClock {
+    pub fn new() -> Self {
+        Self {
+            intervals: VecDeque::with_capacity(PULSES_PER_QUARTER_NOTE),
+            last_timestamp: None,
+            tempo: None,
+        }
+    }
+
+    /// Returns the new tempo when it moved noticeably
+    pub fn pulse(&mut self, timestamp: u64) -> Option<f32> {
+        let last_timestamp = self.last_timestamp.replace(timestamp);
+        let interval = timestamp.saturating_sub(last_timestamp?);
+        if interval == 0 || interval > MAX_PULSE_INTERVAL_MICROSECONDS {
+            self.intervals.clear();
+            return None;
+        }
+
+        if self.intervals.len() == PULSES_PER_QUARTER_NOTE {
+            self.intervals.pop_front();
+        }
+        self.intervals.push_back(interval);
+        if self.intervals.len() < PULSES_PER_QUARTER_NOTE {
+            return None;
+        }
+
+        let beat_length_in_microseconds: u64 = self.intervals.iter().sum();
+        let tempo = 60_000_000.0 / beat_length_in_microseconds as f32;
+        if self
+            .tempo
+            .is_some_and(|current_tempo| (tempo - current_tempo).abs() < TEMPO_CHANGE_THRESHOLD)
+        {
+            return None;
+        }
+        self.tempo = Some(tempo);
+        Some(tempo)
+    }
+}
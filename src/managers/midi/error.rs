@@ -0,0 +1,19 @@
+#[derive(Debug)]
+pub enum Error {
+    Init,
+    Connect,
+    InvalidMapping,
+    MappingNotFound,
+}
+
+impl From<midir::InitError> for Error {
+    fn from(_: midir::InitError) -> Self {
+        Error::Init
+    }
+}
+
+impl<T> From<midir::ConnectError<T>> for Error {
+    fn from(_: midir::ConnectError<T>) -> Self {
+        Error::Connect
+    }
+}
@@ -0,0 +1,308 @@
+use crate::managers::audio_device_settings::AudioDeviceSettingsManager;
+use crate::managers::chain::ChainManager;
+use crate::managers::database::DatabaseManager;
+use crate::managers::dsp::models::TempoSource;
+use crate::managers::dsp::transport::Transport;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::midi::clock::MidiClock;
+use crate::managers::midi::error::Error;
+use crate::managers::midi::models::{MidiMapping, MidiMessage, MidiTarget};
+use crate::managers::presets::{PresetsManager, SetlistMove};
+use crate::processors::frontline::models::ParameterValue;
+use midir::os::unix::VirtualInput;
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+mod clock;
+pub mod error;
+pub mod models;
+
+static CLIENT_NAME: &str = "mojo";
+static VIRTUAL_PORT_NAME: &str = "mojo-input";
+static MAX_CHANNEL: u8 = 15;
+static MAX_DATA_VALUE: u8 = 127;
+// Footswitches send 127 when pressed and 0 when released
+static SWITCH_THRESHOLD: u8 = 64;
+
+#[derive(Default)]
+struct MidiState {
+    mappings: Vec<MidiMapping>,
+    learn_target: Option<MidiTarget>,
+    port_names: Vec<String>,
+}
+
+/// Listens to MIDI controllers: program changes load presets, control changes drive the mapped targets.
+#[derive(Clone)]
+pub struct MidiManager {
+    audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+    chain_manager: ChainManager,
+    connections: Arc<Mutex<Vec<MidiInputConnection<()>>>>,
+    database_manager: DatabaseManager,
+    events_manager: EventsManager,
+    presets_manager: PresetsManager,
+    state: Arc<Mutex<MidiState>>,
+    transport: Arc<Transport>,
+}
+
+impl MidiManager {
+    pub async fn new(
+        audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+        chain_manager: ChainManager,
+        database_manager: DatabaseManager,
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        presets_manager: PresetsManager,
+    ) -> Self {
+        let mappings = database_manager.get_midi_mappings().await;
+        tracing::info!("loaded {} MIDI mappings", mappings.len());
+        let transport = dsp_manager.lock().unwrap().get_transport();
+
+        Self {
+            audio_device_settings_manager,
+            chain_manager,
+            connections: Arc::new(Mutex::new(vec![])),
+            database_manager,
+            events_manager,
+            presets_manager,
+            state: Arc::new(Mutex::new(MidiState {
+                mappings,
+                ..Default::default()
+            })),
+            transport,
+        }
+    }
+
+    /// Connects every available input port, plus a virtual one other applications can send to.
+    pub fn start(&self) -> Result<(), Error> {
+        let (sender, receiver) = unbounded_channel();
+        let mut connections = vec![];
+        let mut port_names = vec![];
+
+        let ports = MidiInput::new(CLIENT_NAME)?.ports();
+        for port in ports.iter() {
+            let input = create_input()?;
+            let port_name = input.port_name(port).unwrap_or_default();
+            match input.connect(port, &port_name, create_callback(sender.clone()), ()) {
+                Ok(connection) => {
+                    tracing::info!("connected to MIDI port {}", port_name);
+                    connections.push(connection);
+                    port_names.push(port_name);
+                }
+                Err(error) => {
+                    tracing::warn!("cannot connect to MIDI port {}: {}", port_name, error)
+                }
+            }
+        }
+
+        connections.push(create_input()?.create_virtual(
+            VIRTUAL_PORT_NAME,
+            create_callback(sender),
+            (),
+        )?);
+        port_names.push(VIRTUAL_PORT_NAME.to_string());
+
+        *self.connections.lock().unwrap() = connections;
+        self.state.lock().unwrap().port_names = port_names;
+
+        tokio::spawn(self.clone().listen(receiver));
+        Ok(())
+    }
+
+    pub fn get_port_names(&self) -> Vec<String> {
+        self.state.lock().unwrap().port_names.clone()
+    }
+
+    pub fn get_mappings(&self) -> Vec<MidiMapping> {
+        self.state.lock().unwrap().mappings.clone()
+    }
+
+    pub async fn add_mapping(&self, mapping: MidiMapping) -> Result<(), Error> {
+        if mapping.channel > MAX_CHANNEL || mapping.controller > MAX_DATA_VALUE {
+            return Err(Error::InvalidMapping);
+        }
+        let mappings = {
+            let mut state = self.state.lock().unwrap();
+            // A controller drives a single target, the latest assignment wins
+            state.mappings.retain(|existing_mapping| {
+                existing_mapping.channel != mapping.channel
+                    || existing_mapping.controller != mapping.controller
+            });
+            state.mappings.push(mapping);
+            state.mappings.clone()
+        };
+        self.save_mappings(mappings).await;
+        Ok(())
+    }
+
+    pub async fn remove_mapping(&self, mapping_index: usize) -> Result<(), Error> {
+        let mappings = {
+            let mut state = self.state.lock().unwrap();
+            if mapping_index >= state.mappings.len() {
+                return Err(Error::MappingNotFound);
+            }
+            state.mappings.remove(mapping_index);
+            state.mappings.clone()
+        };
+        self.save_mappings(mappings).await;
+        Ok(())
+    }
+
+    pub fn get_learn_target(&self) -> Option<MidiTarget> {
+        self.state.lock().unwrap().learn_target.clone()
+    }
+
+    /// The next control change received gets mapped to the target
+    pub fn start_learning(&self, target: MidiTarget) {
+        self.state.lock().unwrap().learn_target = Some(target);
+    }
+
+    pub fn stop_learning(&self) {
+        self.state.lock().unwrap().learn_target = None;
+    }
+
+    async fn save_mappings(&self, mappings: Vec<MidiMapping>) {
+        if let Err(error) = self.database_manager.save_midi_mappings(&mappings).await {
+            tracing::error!("cannot save MIDI mappings: {:?}", error);
+        }
+        self.events_manager
+            .publish(StateEvent::MidiMappingsChanged { mappings });
+    }
+
+    async fn listen(self, mut receiver: UnboundedReceiver<MidiMessage>) {
+        let mut clock = MidiClock::new();
+        while let Some(message) = receiver.recv().await {
+            match message {
+                MidiMessage::ControlChange {
+                    channel,
+                    controller,
+                    value,
+                } => self.handle_control_change(channel, controller, value).await,
+                MidiMessage::ProgramChange { program } => self.handle_program_change(program).await,
+                MidiMessage::Clock { timestamp } => {
+                    if let Some(tempo) = clock.pulse(timestamp) {
+                        self.handle_tempo_change(tempo);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn handle_control_change(&self, channel: u8, controller: u8, value: u8) {
+        let learn_target = self.state.lock().unwrap().learn_target.take();
+        if let Some(target) = learn_target {
+            tracing::info!(
+                "learned MIDI controller {} on channel {}",
+                controller,
+                channel
+            );
+            let _ = self
+                .add_mapping(MidiMapping {
+                    channel,
+                    controller,
+                    target,
+                })
+                .await;
+            return;
+        }
+
+        let targets: Vec<MidiTarget> = self
+            .state
+            .lock()
+            .unwrap()
+            .mappings
+            .iter()
+            .filter(|mapping| mapping.channel == channel && mapping.controller == controller)
+            .map(|mapping| mapping.target.clone())
+            .collect();
+        for target in targets {
+            self.apply(target, value);
+        }
+    }
+
+    /// Program numbers follow the active setlist, or the alphabetical order of the presets
+    async fn handle_program_change(&self, program: u8) {
+        if self.database_manager.get_active_setlist().await.is_some() {
+            if let Err(error) = self
+                .presets_manager
+                .move_in_active_setlist(SetlistMove::Position(program as usize))
+                .await
+            {
+                tracing::warn!("cannot load MIDI program {}: {:?}", program, error);
+            }
+            return;
+        }
+
+        let mut presets = self.database_manager.get_presets_list().await;
+        presets.sort_by(|first, second| first.name.cmp(&second.name));
+        match presets.get(program as usize) {
+            Some(preset) => {
+                self.presets_manager.load_preset(preset.id).await;
+            }
+            None => tracing::warn!("no preset for MIDI program {}", program),
+        }
+    }
+
+    fn handle_tempo_change(&self, tempo: f32) {
+        if self.transport.get_source() == TempoSource::MidiClock {
+            let tempo = self.transport.set_tempo(tempo);
+            self.events_manager.publish(StateEvent::TempoChanged {
+                tempo,
+                source: TempoSource::MidiClock,
+            });
+        }
+    }
+
+    fn apply(&self, target: MidiTarget, value: u8) {
+        let decimal_value = value as f32 / MAX_DATA_VALUE as f32;
+        let event = match target {
+            // Publishing is up to the chain manager for chain edits
+            MidiTarget::Parameter {
+                processor_index,
+                parameter,
+            } => {
+                let _ = self.chain_manager.set_parameter(
+                    processor_index,
+                    parameter,
+                    ParameterValue::Numeric(decimal_value),
+                );
+                None
+            }
+            MidiTarget::Bypass { processor_index } => {
+                let bypassed = value < SWITCH_THRESHOLD;
+                let _ = self.chain_manager.set_bypassed(processor_index, bypassed);
+                None
+            }
+            MidiTarget::Volume => self
+                .audio_device_settings_manager
+                .as_ref()
+                .and_then(|manager| manager.set_volume(decimal_value).ok())
+                .map(|_| StateEvent::VolumeChanged {
+                    value: decimal_value,
+                }),
+        };
+
+        if let Some(event) = event {
+            self.events_manager.publish(event);
+        }
+    }
+}
+
+fn create_input() -> Result<MidiInput, Error> {
+    let mut input = MidiInput::new(CLIENT_NAME)?;
+    input.ignore(Ignore::SysexAndActiveSense);
+    Ok(input)
+}
+
+fn create_callback(
+    sender: UnboundedSender<MidiMessage>,
+) -> impl FnMut(u64, &[u8], &mut ()) + Send + 'static {
+    move |timestamp, bytes, _| {
+        if let Some(message) = MidiMessage::parse(timestamp, bytes) {
+            // The listener only stops when the server shuts down
+            let _ = sender.send(message);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::processors::frontline::models::Parameter;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MidiTarget {
+    Parameter {
+        processor_index: usize,
+        parameter: Parameter,
+    },
+    Bypass {
+        processor_index: usize,
+    },
+    Volume,
+}
+
+/// Binds a Control Change (channel 0-15, controller 0-127) to a target of the rig
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MidiMapping {
+    pub channel: u8,
+    pub controller: u8,
+    pub target: MidiTarget,
+}
+
+pub enum MidiMessage {
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    ProgramChange {
+        program: u8,
+    },
+    Clock {
+        timestamp: u64,
+    },
+}
+
+impl MidiMessage {
+    pub fn parse(timestamp: u64, bytes: &[u8]) -> Option<Self> {
+        let status = *bytes.first()?;
+        if status == 0xF8 {
+            return Some(Self::Clock { timestamp });
+        }
+        let channel = status & 0x0F;
+        match (status & 0xF0, bytes.get(1), bytes.get(2)) {
+            (0xB0, Some(controller), Some(value)) => Some(Self::ControlChange {
+                channel,
+                controller: *controller,
+                value: *value,
+            }),
+            (0xC0, Some(program), _) => Some(Self::ProgramChange { program: *program }),
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,14 @@
+pub mod analysis;
 pub mod audio_device_settings;
 pub mod cache;
+pub mod chain;
 pub mod database;
 pub mod dsp;
+pub mod events;
 pub mod file_system;
+pub mod history;
+pub mod midi;
+pub mod osc;
 pub mod performance;
+pub mod presets;
+pub mod session;
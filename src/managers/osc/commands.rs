@@ -0,0 +1,220 @@
+use crate::managers::dsp::models::ProcessorType;
+use crate::managers::osc::error::Error;
+use crate::managers::presets::SetlistMove;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use rosc::{OscMessage, OscType};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+/// What an incoming message asks for, its address mirrors the REST API
+pub enum OscCommand {
+    SetParameter {
+        index: usize,
+        parameter: Parameter,
+        value: ParameterValue,
+    },
+    SetBypassed {
+        index: usize,
+        bypassed: bool,
+    },
+    CreateProcessor {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    SwapProcessor {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    MoveProcessor {
+        index: usize,
+        destination_index: usize,
+    },
+    DeleteProcessor {
+        index: usize,
+    },
+    LoadPreset {
+        id: Uuid,
+    },
+    SelectScene {
+        index: usize,
+    },
+    MoveInSetlist {
+        setlist_move: SetlistMove,
+    },
+    SetExpression {
+        position: f32,
+    },
+    SetTempo {
+        tempo: f32,
+    },
+    TapTempo,
+    SetInputGain {
+        value: f32,
+    },
+    SetVolume {
+        value: f32,
+    },
+    SetMicBias {
+        on: bool,
+    },
+    RegisterFeedback {
+        port: Option<u16>,
+    },
+    UnregisterFeedback {
+        port: Option<u16>,
+    },
+}
+
+impl TryFrom<&OscMessage> for OscCommand {
+    type Error = Error;
+
+    fn try_from(message: &OscMessage) -> Result<Self, Self::Error> {
+        let invalid_address = || Error::InvalidAddress;
+        let segments: Vec<&str> = message.addr.trim_matches('/').split('/').collect();
+        let arguments = Arguments {
+            values: &message.args,
+        };
+
+        Ok(match segments.as_slice() {
+            ["processors", "create"] => Self::CreateProcessor {
+                index: arguments.get_index(0)?,
+                processor_type: arguments.get_enum(1)?,
+            },
+            ["processors", index] => Self::SwapProcessor {
+                index: index.parse().map_err(|_| invalid_address())?,
+                processor_type: arguments.get_enum(0)?,
+            },
+            ["processors", index, action] => {
+                let index = index.parse().map_err(|_| invalid_address())?;
+                match *action {
+                    "bypassed" => Self::SetBypassed {
+                        index,
+                        bypassed: arguments.get_bool(0)?,
+                    },
+                    "move" => Self::MoveProcessor {
+                        index,
+                        destination_index: arguments.get_index(0)?,
+                    },
+                    "delete" => Self::DeleteProcessor { index },
+                    _ => return Err(invalid_address()),
+                }
+            }
+            ["processors", index, "parameters", parameter] => Self::SetParameter {
+                index: index.parse().map_err(|_| invalid_address())?,
+                parameter: parse_enum(parameter).ok_or_else(invalid_address)?,
+                value: arguments.get_parameter_value(0)?,
+            },
+            ["presets", "load"] => Self::LoadPreset {
+                id: arguments
+                    .get_string(0)?
+                    .parse()
+                    .map_err(|_| arguments.invalid())?,
+            },
+            ["presets", "current", "scenes", index, "select"] => Self::SelectScene {
+                index: index.parse().map_err(|_| invalid_address())?,
+            },
+            ["setlists", "active", "next"] => Self::MoveInSetlist {
+                setlist_move: SetlistMove::Next,
+            },
+            ["setlists", "active", "previous"] => Self::MoveInSetlist {
+                setlist_move: SetlistMove::Previous,
+            },
+            ["setlists", "active", "position"] => Self::MoveInSetlist {
+                setlist_move: SetlistMove::Position(arguments.get_index(0)?),
+            },
+            ["expression"] => Self::SetExpression {
+                position: arguments.get_float(0)?,
+            },
+            ["tempo"] => Self::SetTempo {
+                tempo: arguments.get_float(0)?,
+            },
+            ["tempo", "tap"] => Self::TapTempo,
+            ["device", "input-gain"] => Self::SetInputGain {
+                value: arguments.get_float(0)?,
+            },
+            ["device", "volume"] => Self::SetVolume {
+                value: arguments.get_float(0)?,
+            },
+            ["device", "mic-bias"] => Self::SetMicBias {
+                on: arguments.get_bool(0)?,
+            },
+            ["feedback", "register"] => Self::RegisterFeedback {
+                port: arguments.get_optional_port(0)?,
+            },
+            ["feedback", "unregister"] => Self::UnregisterFeedback {
+                port: arguments.get_optional_port(0)?,
+            },
+            _ => return Err(invalid_address()),
+        })
+    }
+}
+
+struct Arguments<'a> {
+    values: &'a [OscType],
+}
+
+impl Arguments<'_> {
+    fn invalid(&self) -> Error {
+        Error::InvalidArguments
+    }
+
+    fn get_float(&self, index: usize) -> Result<f32, Error> {
+        match self.values.get(index) {
+            Some(OscType::Float(value)) => Ok(*value),
+            Some(OscType::Double(value)) => Ok(*value as f32),
+            Some(OscType::Int(value)) => Ok(*value as f32),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn get_index(&self, index: usize) -> Result<usize, Error> {
+        match self.values.get(index) {
+            Some(OscType::Int(value)) if *value >= 0 => Ok(*value as usize),
+            Some(OscType::Float(value)) if *value >= 0.0 => Ok(*value as usize),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    /// Toggle buttons usually send 0/1 floats rather than booleans
+    fn get_bool(&self, index: usize) -> Result<bool, Error> {
+        match self.values.get(index) {
+            Some(OscType::Bool(value)) => Ok(*value),
+            Some(OscType::Int(value)) => Ok(*value != 0),
+            Some(OscType::Float(value)) => Ok(*value >= 0.5),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn get_string(&self, index: usize) -> Result<&str, Error> {
+        match self.values.get(index) {
+            Some(OscType::String(value)) => Ok(value.as_str()),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn get_enum<Value: DeserializeOwned>(&self, index: usize) -> Result<Value, Error> {
+        parse_enum(self.get_string(index)?).ok_or_else(|| self.invalid())
+    }
+
+    fn get_parameter_value(&self, index: usize) -> Result<ParameterValue, Error> {
+        match self.values.get(index) {
+            Some(OscType::String(value)) => Ok(ParameterValue::String(value.clone())),
+            _ => Ok(ParameterValue::Numeric(self.get_float(index)?)),
+        }
+    }
+
+    fn get_optional_port(&self, index: usize) -> Result<Option<u16>, Error> {
+        match self.values.get(index) {
+            None => Ok(None),
+            Some(OscType::Int(value)) => {
+                u16::try_from(*value).map(Some).map_err(|_| self.invalid())
+            }
+            _ => Err(self.invalid()),
+        }
+    }
+}
+
+/// Enum names follow the same snake case used by the REST API
+fn parse_enum<Value: DeserializeOwned>(name: &str) -> Option<Value> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
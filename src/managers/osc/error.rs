@@ -0,0 +1,70 @@
+use crate::managers::chain::error::Error as ChainError;
+use crate::managers::dsp::error::{TempoError, TransformProcessorError};
+use crate::managers::presets::error::{SceneError, SetlistError};
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Bind,
+    Decode,
+    InvalidAddress,
+    InvalidArguments,
+    DeviceNotAvailable,
+    PresetNotFound,
+    SceneNotFound,
+    SetlistNavigation,
+    ProcessorNotFound,
+    SlavedToMidiClock,
+    UnsupportedParameter,
+}
+
+impl From<TransformProcessorError> for Error {
+    fn from(value: TransformProcessorError) -> Self {
+        match value {
+            TransformProcessorError::NotFound => Error::ProcessorNotFound,
+        }
+    }
+}
+
+impl From<ChainError> for Error {
+    fn from(value: ChainError) -> Self {
+        match value {
+            ChainError::ProcessorNotFound => Error::ProcessorNotFound,
+            ChainError::UnsupportedParameter => Error::UnsupportedParameter,
+        }
+    }
+}
+
+impl From<TempoError> for Error {
+    fn from(value: TempoError) -> Self {
+        match value {
+            TempoError::SlavedToMidiClock => Error::SlavedToMidiClock,
+        }
+    }
+}
+
+impl From<SceneError> for Error {
+    fn from(value: SceneError) -> Self {
+        match value {
+            SceneError::NotFound => Error::SceneNotFound,
+        }
+    }
+}
+
+impl From<SetlistError> for Error {
+    fn from(_: SetlistError) -> Self {
+        Error::SetlistNavigation
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(_: io::Error) -> Self {
+        Error::Bind
+    }
+}
+
+impl From<rosc::OscError> for Error {
+    fn from(_: rosc::OscError) -> Self {
+        Error::Decode
+    }
+}
@@ -0,0 +1,143 @@
+use crate::managers::dsp::models::ProcessorInfo;
+use crate::managers::events::models::StateEvent;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use rosc::{OscMessage, OscType};
+
+/// Translates a change of the rig into the messages a control surface needs to stay in sync
+pub fn get_feedback_messages(event: &StateEvent) -> Vec<OscMessage> {
+    match event {
+        StateEvent::ProcessorCreated { index, processor }
+        | StateEvent::ProcessorSwapped { index, processor } => {
+            get_processor_messages(*index, processor)
+        }
+        StateEvent::ProcessorBypassed { index, bypassed } => {
+            vec![get_bypassed_message(*index, *bypassed)]
+        }
+        StateEvent::ParameterChanged {
+            index,
+            parameter,
+            value,
+        } => vec![get_parameter_message(*index, parameter, value)],
+        StateEvent::PresetLoaded { id, processors } => get_snapshot_messages(
+            Some(create_message(
+                "/presets/loaded".to_string(),
+                OscType::String(id.to_string()),
+            )),
+            processors,
+        ),
+        StateEvent::SceneSelected { index, processors } => get_snapshot_messages(
+            Some(create_message(
+                "/presets/current/scenes/selected".to_string(),
+                OscType::Int(*index as i32),
+            )),
+            processors,
+        ),
+        StateEvent::SetlistPositionChanged { position, .. } => vec![create_message(
+            "/setlists/active/position".to_string(),
+            OscType::Int(*position as i32),
+        )],
+        StateEvent::ExpressionChanged { position } => vec![create_message(
+            "/expression".to_string(),
+            OscType::Float(*position),
+        )],
+        StateEvent::TempoChanged { tempo, .. } => {
+            vec![create_message("/tempo".to_string(), OscType::Float(*tempo))]
+        }
+        StateEvent::InputGainChanged { value } => vec![create_message(
+            "/device/input-gain".to_string(),
+            OscType::Float(*value),
+        )],
+        StateEvent::VolumeChanged { value } => vec![create_message(
+            "/device/volume".to_string(),
+            OscType::Float(*value),
+        )],
+        StateEvent::MicBiasChanged { on } => vec![create_message(
+            "/device/mic-bias".to_string(),
+            OscType::Int(*on as i32),
+        )],
+        StateEvent::EngineStateChanged { is_running } => vec![create_message(
+            "/engine/running".to_string(),
+            OscType::Int(*is_running as i32),
+        )],
+        StateEvent::ProcessorDeleted { .. }
+        | StateEvent::ProcessorMoved { .. }
+        | StateEvent::MidiMappingsChanged { .. }
+        | StateEvent::AudioConfigChanged { .. }
+        | StateEvent::ScenesChanged { .. } => vec![],
+    }
+}
+
+/// The whole chain, sent to new clients and whenever the chain layout changes
+pub fn get_snapshot_messages(
+    header: Option<OscMessage>,
+    processors: &[ProcessorInfo],
+) -> Vec<OscMessage> {
+    header
+        .into_iter()
+        .chain(std::iter::once(create_message(
+            "/processors/count".to_string(),
+            OscType::Int(processors.len() as i32),
+        )))
+        .chain(
+            processors
+                .iter()
+                .enumerate()
+                .flat_map(|(index, processor)| get_processor_messages(index, processor)),
+        )
+        .collect()
+}
+
+fn get_processor_messages(index: usize, processor: &ProcessorInfo) -> Vec<OscMessage> {
+    let numeric_parameters = processor
+        .parameters
+        .numeric
+        .iter()
+        .map(|(parameter, value)| {
+            get_parameter_message(index, parameter, &ParameterValue::Numeric(*value))
+        });
+    let string_parameters = processor
+        .parameters
+        .string
+        .iter()
+        .map(|(parameter, value)| {
+            get_parameter_message(index, parameter, &ParameterValue::String(value.clone()))
+        });
+
+    std::iter::once(get_bypassed_message(index, processor.settings.bypassed))
+        .chain(numeric_parameters)
+        .chain(string_parameters)
+        .collect()
+}
+
+fn get_bypassed_message(index: usize, bypassed: bool) -> OscMessage {
+    create_message(
+        format!("/processors/{}/bypassed", index),
+        OscType::Int(bypassed as i32),
+    )
+}
+
+fn get_parameter_message(
+    index: usize,
+    parameter: &Parameter,
+    value: &ParameterValue,
+) -> OscMessage {
+    let parameter_name = match serde_json::to_value(parameter) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    };
+    let argument = match value {
+        ParameterValue::Numeric(value) => OscType::Float(*value),
+        ParameterValue::String(value) => OscType::String(value.clone()),
+    };
+    create_message(
+        format!("/processors/{}/parameters/{}", index, parameter_name),
+        argument,
+    )
+}
+
+fn create_message(addr: String, argument: OscType) -> OscMessage {
+    OscMessage {
+        addr,
+        args: vec![argument],
+    }
+}
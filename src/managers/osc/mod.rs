@@ -0,0 +1,292 @@
+use crate::managers::audio_device_settings::AudioDeviceSettingsManager;
+use crate::managers::chain::ChainManager;
+use crate::managers::dsp::models::TempoSource;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::osc::commands::OscCommand;
+use crate::managers::osc::error::Error;
+use crate::managers::osc::feedback::{get_feedback_messages, get_snapshot_messages};
+use crate::managers::presets::PresetsManager;
+use rosc::decoder::{decode_udp, MTU};
+use rosc::encoder::encode;
+use rosc::{OscMessage, OscPacket};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+use tokio::sync::broadcast::error::RecvError;
+
+mod commands;
+pub mod error;
+mod feedback;
+
+/// Open Sound Control endpoint: drives the rig over UDP and echoes its changes to registered clients.
+#[derive(Clone)]
+pub struct OscManager {
+    audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+    chain_manager: ChainManager,
+    dsp_manager: Arc<Mutex<DSPManager>>,
+    events_manager: EventsManager,
+    feedback_clients: Arc<Mutex<HashSet<SocketAddr>>>,
+    presets_manager: PresetsManager,
+    socket: Arc<UdpSocket>,
+}
+
+impl OscManager {
+    pub async fn new(
+        connection_string: String,
+        audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+        chain_manager: ChainManager,
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        presets_manager: PresetsManager,
+    ) -> Result<Self, Error> {
+        let socket = UdpSocket::bind(&connection_string).await?;
+        tracing::info!("OSC server listening on {}", connection_string);
+
+        Ok(Self {
+            audio_device_settings_manager,
+            chain_manager,
+            dsp_manager,
+            events_manager,
+            feedback_clients: Arc::new(Mutex::new(HashSet::new())),
+            presets_manager,
+            socket: Arc::new(socket),
+        })
+    }
+
+    pub fn start(&self) {
+        tokio::spawn(self.clone().listen());
+        tokio::spawn(self.clone().send_feedback());
+    }
+
+    async fn listen(self) {
+        let mut buffer = [0u8; MTU];
+        loop {
+            let (size, source) = match self.socket.recv_from(&mut buffer).await {
+                Ok(received) => received,
+                Err(error) => {
+                    tracing::warn!("cannot receive OSC packet: {}", error);
+                    continue;
+                }
+            };
+            let messages = match decode_udp(&buffer[..size]) {
+                Ok((_, packet)) => flatten_packet(packet),
+                Err(_) => {
+                    tracing::warn!("cannot decode OSC packet from {}", source);
+                    continue;
+                }
+            };
+            for message in messages {
+                let result = match OscCommand::try_from(&message) {
+                    Ok(command) => self.handle_command(command, source).await,
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = result {
+                    tracing::warn!("cannot handle OSC message {}: {:?}", message.addr, error);
+                }
+            }
+        }
+    }
+
+    async fn handle_command(&self, command: OscCommand, source: SocketAddr) -> Result<(), Error> {
+        let event = match command {
+            // Publishing is up to the chain manager for chain edits
+            OscCommand::SetParameter {
+                index,
+                parameter,
+                value,
+            } => {
+                return self
+                    .chain_manager
+                    .set_parameter(index, parameter, value)
+                    .map(|_| ())
+                    .map_err(Error::from);
+            }
+            OscCommand::SetBypassed { index, bypassed } => {
+                return Ok(self.chain_manager.set_bypassed(index, bypassed)?);
+            }
+            OscCommand::CreateProcessor {
+                index,
+                processor_type,
+            } => {
+                return self
+                    .chain_manager
+                    .create_processor(index, &processor_type)
+                    .map(|_| ())
+                    .map_err(Error::from);
+            }
+            OscCommand::SwapProcessor {
+                index,
+                processor_type,
+            } => {
+                return self
+                    .chain_manager
+                    .swap_processor(index, &processor_type)
+                    .map(|_| ())
+                    .map_err(Error::from);
+            }
+            OscCommand::MoveProcessor {
+                index,
+                destination_index,
+            } => {
+                return Ok(self
+                    .chain_manager
+                    .move_processor(index, destination_index)?);
+            }
+            OscCommand::DeleteProcessor { index } => {
+                return Ok(self.chain_manager.delete_processor(index)?);
+            }
+            OscCommand::LoadPreset { id } => {
+                // Publishing is up to the presets manager
+                return self
+                    .presets_manager
+                    .load_preset(id)
+                    .await
+                    .map(|_| ())
+                    .ok_or(Error::PresetNotFound);
+            }
+            OscCommand::SelectScene { index } => {
+                return Ok(self.presets_manager.select_scene(index)?);
+            }
+            OscCommand::MoveInSetlist { setlist_move } => {
+                self.presets_manager
+                    .move_in_active_setlist(setlist_move)
+                    .await?;
+                return Ok(());
+            }
+            OscCommand::SetExpression { position } => {
+                let mut dsp_manager = self.dsp_manager.lock().unwrap();
+                dsp_manager.set_expression_position(position);
+                StateEvent::ExpressionChanged {
+                    position: dsp_manager.get_expression_position(),
+                }
+            }
+            OscCommand::SetTempo { tempo } => StateEvent::TempoChanged {
+                tempo: self.dsp_manager.lock().unwrap().set_tempo(tempo)?,
+                source: TempoSource::Internal,
+            },
+            OscCommand::TapTempo => match self.dsp_manager.lock().unwrap().tap_tempo()? {
+                Some(tempo) => StateEvent::TempoChanged {
+                    tempo,
+                    source: TempoSource::Internal,
+                },
+                None => return Ok(()),
+            },
+            OscCommand::SetInputGain { value } => {
+                self.get_audio_device_settings_manager()?
+                    .set_input_gain(value)
+                    .map_err(|_| Error::DeviceNotAvailable)?;
+                StateEvent::InputGainChanged { value }
+            }
+            OscCommand::SetVolume { value } => {
+                self.get_audio_device_settings_manager()?
+                    .set_volume(value)
+                    .map_err(|_| Error::DeviceNotAvailable)?;
+                StateEvent::VolumeChanged { value }
+            }
+            OscCommand::SetMicBias { on } => {
+                self.get_audio_device_settings_manager()?
+                    .set_mic_bias(on)
+                    .map_err(|_| Error::DeviceNotAvailable)?;
+                StateEvent::MicBiasChanged { on }
+            }
+            OscCommand::RegisterFeedback { port } => {
+                let client = get_client_address(source, port);
+                self.feedback_clients.lock().unwrap().insert(client);
+                tracing::info!("OSC feedback enabled for {}", client);
+                self.send_snapshot(&[client]).await;
+                return Ok(());
+            }
+            OscCommand::UnregisterFeedback { port } => {
+                let client = get_client_address(source, port);
+                self.feedback_clients.lock().unwrap().remove(&client);
+                return Ok(());
+            }
+        };
+
+        self.events_manager.publish(event);
+        Ok(())
+    }
+
+    fn get_audio_device_settings_manager(&self) -> Result<&AudioDeviceSettingsManager, Error> {
+        self.audio_device_settings_manager
+            .as_deref()
+            .ok_or(Error::DeviceNotAvailable)
+    }
+
+    async fn send_feedback(self) {
+        let mut events = self.events_manager.subscribe();
+        loop {
+            let messages = match events.recv().await {
+                // Indexes shifted: the whole chain is resent
+                Ok(StateEvent::ProcessorDeleted { .. }) | Ok(StateEvent::ProcessorMoved { .. }) => {
+                    self.get_snapshot_messages()
+                }
+                Ok(event) => get_feedback_messages(&event),
+                Err(RecvError::Lagged(_)) => self.get_snapshot_messages(),
+                Err(RecvError::Closed) => break,
+            };
+            let clients: Vec<SocketAddr> = self
+                .feedback_clients
+                .lock()
+                .unwrap()
+                .iter()
+                .copied()
+                .collect();
+            self.send(&clients, messages).await;
+        }
+    }
+
+    async fn send_snapshot(&self, clients: &[SocketAddr]) {
+        let messages = self.get_snapshot_messages();
+        self.send(clients, messages).await;
+    }
+
+    fn get_snapshot_messages(&self) -> Vec<OscMessage> {
+        get_snapshot_messages(
+            None,
+            &self.dsp_manager.lock().unwrap().get_processors_info(),
+        )
+    }
+
+    async fn send(&self, clients: &[SocketAddr], messages: Vec<OscMessage>) {
+        if clients.is_empty() {
+            return;
+        }
+        for message in messages {
+            let packet = match encode(&OscPacket::Message(message)) {
+                Ok(packet) => packet,
+                Err(error) => {
+                    tracing::error!("cannot encode OSC message: {}", error);
+                    continue;
+                }
+            };
+            for client in clients {
+                if let Err(error) = self.socket.send_to(&packet, client).await {
+                    tracing::warn!("cannot send OSC feedback to {}: {}", client, error);
+                }
+            }
+        }
+    }
+}
+
+fn flatten_packet(packet: OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(message) => vec![message],
+        OscPacket::Bundle(bundle) => bundle
+            .content
+            .into_iter()
+            .flat_map(flatten_packet)
+            .collect(),
+    }
+}
+
+/// Clients often listen on a different port than the one they send from
+fn get_client_address(source: SocketAddr, port: Option<u16>) -> SocketAddr {
+    match port {
+        Some(port) => SocketAddr::new(source.ip(), port),
+        None => source,
+    }
+}
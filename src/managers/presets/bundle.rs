@@ -0,0 +1,192 @@
+use crate::managers::database::migrations::migrate_preset;
+use crate::managers::database::models::Preset;
+use crate::managers::dsp::models::ProcessorInfo;
+use crate::managers::file_system::FileSystemManager;
+use crate::managers::presets::error::BundleError;
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::{create_processor_from_type, is_processor_type_supported};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub static BUNDLE_SCHEMA_VERSION: u32 = 1;
+static IMPORTED_ASSETS_DIRECTORY_NAME: &str = "imported";
+// Processors are only built to ask which parameters they have, any valid setting works
+static VALIDATION_SAMPLE_RATE: u32 = 48000;
+static VALIDATION_BUFFER_SIZE: usize = 512;
+
+/// A preset with every file it needs, so that it can be moved to another rig
+#[derive(Serialize, Deserialize)]
+pub struct PresetBundle {
+    pub schema_version: u32,
+    /// Kept as a raw document, so that presets from older rigs are migrated on import
+    pub preset: Value,
+    pub assets: Vec<BundleAsset>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundleAsset {
+    /// Relative to the data root, which is also how the preset refers to it
+    pub path: String,
+    /// Base64 encoded
+    pub content: String,
+}
+
+pub struct ImportedPreset {
+    pub preset: Preset,
+    pub missing_assets: Vec<String>,
+}
+
+pub fn create_bundle(mut preset: Preset, file_system_manager: &FileSystemManager) -> PresetBundle {
+    let mut assets: Vec<BundleAsset> = vec![];
+    for processor in get_processors_mut(&mut preset) {
+        let Some(path) = processor.parameters.string.get_mut(&Parameter::FilePath) else {
+            continue;
+        };
+        let Some(relative_path) = file_system_manager.get_relative_path(path) else {
+            tracing::warn!("asset {} is outside the data folder, skipping it", path);
+            continue;
+        };
+        if !assets.iter().any(|asset| asset.path == relative_path) {
+            match file_system_manager.read_file(&relative_path) {
+                Ok(content) => assets.push(BundleAsset {
+                    path: relative_path.clone(),
+                    content: STANDARD.encode(content),
+                }),
+                Err(_) => tracing::warn!("asset {} not found, skipping it", relative_path),
+            }
+        }
+        *path = relative_path;
+    }
+
+    PresetBundle {
+        schema_version: BUNDLE_SCHEMA_VERSION,
+        preset: serde_json::to_value(preset).unwrap_or_default(),
+        assets,
+    }
+}
+
+/// Validates the bundle and unpacks its assets. The preset gets a new ID, so it never
+/// overwrites an existing one. Files the bundle doesn't ship are dropped from the preset and
+/// reported as missing, so an imported preset can only point to its own assets.
+pub fn open_bundle(
+    bundle: PresetBundle,
+    file_system_manager: &FileSystemManager,
+) -> Result<ImportedPreset, BundleError> {
+    if bundle.schema_version > BUNDLE_SCHEMA_VERSION {
+        return Err(BundleError::UnsupportedSchemaVersion);
+    }
+    let mut preset = migrate_preset(bundle.preset).ok_or(BundleError::InvalidPreset)?;
+    preset.id = Uuid::new_v4();
+    preset.is_default = false;
+    preset.metadata.version = 0;
+    for processor in get_processors_mut(&mut preset) {
+        validate_processor(processor)?;
+    }
+
+    // Everything is validated before the first file is written
+    let mut assets: HashMap<String, Vec<u8>> = HashMap::new();
+    for asset in bundle.assets {
+        file_system_manager.get_absolute_path(&asset.path)?;
+        let content = STANDARD
+            .decode(&asset.content)
+            .map_err(|_| BundleError::InvalidAsset)?;
+        assets.insert(asset.path, content);
+    }
+    let mut missing_assets: Vec<String> = vec![];
+    for processor in get_processors_mut(&mut preset) {
+        let Some(path) = processor.parameters.string.get(&Parameter::FilePath) else {
+            continue;
+        };
+        if !assets.contains_key(path) {
+            if !missing_assets.contains(path) {
+                missing_assets.push(path.clone());
+            }
+            processor.parameters.string.remove(&Parameter::FilePath);
+        }
+    }
+
+    let preset_id = preset.id;
+    let mut asset_paths: HashMap<String, String> = HashMap::new();
+    for processor in get_processors_mut(&mut preset) {
+        let Some(path) = processor.parameters.string.get_mut(&Parameter::FilePath) else {
+            continue;
+        };
+        if !asset_paths.contains_key(path) {
+            let local_path = write_asset(file_system_manager, preset_id, path, &assets[path])?;
+            asset_paths.insert(path.clone(), local_path);
+        }
+        *path = asset_paths[path].clone();
+    }
+
+    Ok(ImportedPreset {
+        preset,
+        missing_assets,
+    })
+}
+
+/// Returns the absolute path of the file the preset should use
+fn write_asset(
+    file_system_manager: &FileSystemManager,
+    preset_id: Uuid,
+    path: &str,
+    content: &[u8],
+) -> Result<String, BundleError> {
+    Ok(match file_system_manager.read_file(path) {
+        Ok(existing_content) if existing_content == content => {
+            file_system_manager.get_absolute_path(path)?
+        }
+        // Same name, different file: the local one is kept untouched
+        Ok(_) => file_system_manager.write_file(
+            &format!("{}/{}/{}", IMPORTED_ASSETS_DIRECTORY_NAME, preset_id, path),
+            content,
+        )?,
+        Err(_) => file_system_manager.write_file(path, content)?,
+    })
+}
+
+/// Only processors the engine can build are accepted, with parameters they actually have
+fn validate_processor(processor: &ProcessorInfo) -> Result<(), BundleError> {
+    if !is_processor_type_supported(&processor.processor_type) {
+        return Err(BundleError::UnsupportedProcessor);
+    }
+    let reference_processor = create_processor_from_type(
+        &processor.processor_type,
+        &VALIDATION_SAMPLE_RATE,
+        &VALIDATION_BUFFER_SIZE,
+    );
+    let are_numeric_parameters_valid =
+        processor
+            .parameters
+            .numeric
+            .iter()
+            .all(|(parameter, value)| {
+                matches!(
+                    reference_processor.get_parameter(*parameter),
+                    Some(ParameterValue::Numeric(_))
+                ) && (0.0..=1.0).contains(value)
+            });
+    let are_string_parameters_valid = processor.parameters.string.keys().all(|parameter| {
+        matches!(
+            reference_processor.get_parameter(*parameter),
+            Some(ParameterValue::String(_))
+        )
+    });
+    if are_numeric_parameters_valid && are_string_parameters_valid {
+        Ok(())
+    } else {
+        Err(BundleError::InvalidParameter)
+    }
+}
+
+fn get_processors_mut(preset: &mut Preset) -> impl Iterator<Item = &mut ProcessorInfo> {
+    preset.processors.iter_mut().chain(
+        preset
+            .scenes
+            .iter_mut()
+            .flat_map(|scene| scene.processors.iter_mut()),
+    )
+}
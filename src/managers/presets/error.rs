@@ -0,0 +1,53 @@
+use crate::managers::database::error::Error as DatabaseError;
+use crate::managers::file_system::error::Error as FileSystemError;
+
+#[derive(Debug)]
+pub enum SceneError {
+    NotFound,
+}
+
+#[derive(Debug)]
+pub enum SetlistError {
+    Database,
+    NoActiveSetlist,
+    NotFound,
+    PositionOutOfRange,
+    PresetNotFound,
+}
+
+impl From<DatabaseError> for SetlistError {
+    fn from(_: DatabaseError) -> Self {
+        SetlistError::Database
+    }
+}
+
+#[derive(Debug)]
+pub enum RevisionError {
+    Database,
+    NotFound,
+}
+
+impl From<DatabaseError> for RevisionError {
+    fn from(_: DatabaseError) -> Self {
+        RevisionError::Database
+    }
+}
+
+#[derive(Debug)]
+pub enum BundleError {
+    FileSystem,
+    InvalidAsset,
+    InvalidParameter,
+    InvalidPreset,
+    UnsupportedProcessor,
+    UnsupportedSchemaVersion,
+}
+
+impl From<FileSystemError> for BundleError {
+    fn from(value: FileSystemError) -> Self {
+        match value {
+            FileSystemError::InvalidPath => BundleError::InvalidAsset,
+            _ => BundleError::FileSystem,
+        }
+    }
+}
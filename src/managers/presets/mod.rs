@@ -0,0 +1,385 @@
+use crate::managers::cache::CacheManager;
+use crate::managers::database::error::Error as DatabaseError;
+use crate::managers::database::models::{Preset, PresetRevision, Scene, Setlist};
+use crate::managers::database::DatabaseManager;
+use crate::managers::dsp::models::TempoSource;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::history::HistoryManager;
+use crate::managers::presets::error::{RevisionError, SceneError, SetlistError};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+pub mod bundle;
+pub mod error;
+pub mod revisions;
+
+pub enum SetlistMove {
+    Next,
+    Previous,
+    Position(usize),
+}
+
+/// Switches the rig between presets, whoever asks for it (HTTP, MIDI, ...).
+#[derive(Clone)]
+pub struct PresetsManager {
+    cache_manager: Arc<Mutex<CacheManager>>,
+    database_manager: DatabaseManager,
+    dsp_manager: Arc<Mutex<DSPManager>>,
+    events_manager: EventsManager,
+    history_manager: HistoryManager,
+    max_preset_revisions: usize,
+}
+
+impl PresetsManager {
+    pub fn new(
+        cache_manager: Arc<Mutex<CacheManager>>,
+        database_manager: DatabaseManager,
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        history_manager: HistoryManager,
+        max_preset_revisions: usize,
+    ) -> Self {
+        Self {
+            cache_manager,
+            database_manager,
+            dsp_manager,
+            events_manager,
+            history_manager,
+            max_preset_revisions,
+        }
+    }
+
+    pub async fn load_preset(&self, preset_id: Uuid) -> Option<Preset> {
+        let preset = self.database_manager.get_preset(preset_id).await?;
+        {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            cache_manager.current_preset_id = Some(preset_id);
+            cache_manager.current_scenes = preset.scenes.clone();
+            cache_manager.current_scene_index = None;
+        }
+        let tempo = {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            dsp_manager.load_processors(&preset.processors);
+            dsp_manager.set_expression_assignments(&preset.expression_assignments);
+            self.history_manager.clear();
+            // An external clock keeps the lead over the stored tempo
+            dsp_manager.set_tempo(preset.tempo).ok()
+        };
+        self.events_manager.publish(StateEvent::PresetLoaded {
+            id: preset_id,
+            processors: preset.processors.clone(),
+        });
+        if let Some(tempo) = tempo {
+            self.events_manager.publish(StateEvent::TempoChanged {
+                tempo,
+                source: TempoSource::Internal,
+            });
+        }
+
+        Some(preset)
+    }
+
+    pub fn reset_current_preset(&self) -> Uuid {
+        let id = Uuid::new_v4();
+        {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            cache_manager.current_preset_id = Some(id);
+            cache_manager.current_scenes.clear();
+            cache_manager.current_scene_index = None;
+        }
+        self.dsp_manager.lock().unwrap().clear_all_processors();
+        self.history_manager.clear();
+        self.events_manager.publish(StateEvent::PresetLoaded {
+            id,
+            processors: vec![],
+        });
+
+        id
+    }
+
+    /// Stores the preset, replacing the previous version, and appends it to its revisions. The
+    /// version of the preset is the one the changes were based on: if it was stored again in
+    /// the meantime, the save fails with `VersionConflict`. If the revision cannot be stored,
+    /// the previous version is put back, so that the latest revision always matches the preset.
+    pub async fn save_preset(
+        &self,
+        mut preset: Preset,
+        message: Option<String>,
+    ) -> Result<PresetRevision, DatabaseError> {
+        let expected_version = preset.metadata.version;
+        preset.metadata.version = expected_version + 1;
+        let timestamp = get_timestamp();
+        if preset.metadata.created_at == 0 {
+            preset.metadata.created_at = timestamp;
+        }
+        preset.metadata.modified_at = timestamp;
+        let revision = PresetRevision {
+            id: Uuid::new_v4(),
+            preset_id: preset.id,
+            timestamp,
+            message,
+            preset,
+        };
+        let previous_preset = self.database_manager.get_preset(revision.preset_id).await;
+        self.database_manager
+            .save_preset(revision.preset.clone(), expected_version)
+            .await?;
+        if let Err(error) = self.database_manager.save_preset_revision(&revision).await {
+            let rollback_result = match previous_preset {
+                Some(previous_preset) => {
+                    self.database_manager
+                        .save_preset(previous_preset, revision.preset.metadata.version)
+                        .await
+                }
+                None => {
+                    self.database_manager
+                        .delete_preset(revision.preset_id)
+                        .await;
+                    Ok(())
+                }
+            };
+            if let Err(rollback_error) = rollback_result {
+                tracing::error!("cannot restore the previous preset: {:?}", rollback_error);
+            }
+            return Err(error);
+        }
+        if let Err(error) = self
+            .database_manager
+            .delete_old_preset_revisions(revision.preset_id, self.max_preset_revisions)
+            .await
+        {
+            tracing::warn!("cannot delete old preset revisions: {:?}", error);
+        }
+        Ok(revision)
+    }
+
+    /// Saves the preset under a new ID, which becomes the current preset. The original one is
+    /// left untouched.
+    pub async fn save_preset_as(
+        &self,
+        mut preset: Preset,
+        message: Option<String>,
+    ) -> Result<PresetRevision, DatabaseError> {
+        preset.id = Uuid::new_v4();
+        preset.is_default = false;
+        preset.metadata.created_at = 0;
+        preset.metadata.version = 0;
+        let revision = self.save_preset(preset, message).await?;
+        self.cache_manager.lock().unwrap().current_preset_id = Some(revision.preset_id);
+        self.events_manager.publish(StateEvent::PresetLoaded {
+            id: revision.preset_id,
+            processors: revision.preset.processors.clone(),
+        });
+        Ok(revision)
+    }
+
+    /// Saves the revision as the latest version of the preset, reloading it if it is playing
+    pub async fn restore_preset_revision(
+        &self,
+        preset_id: Uuid,
+        revision_id: Uuid,
+    ) -> Result<Preset, RevisionError> {
+        let revision = self
+            .database_manager
+            .get_preset_revision(revision_id)
+            .await
+            .filter(|revision| revision.preset_id == preset_id)
+            .ok_or(RevisionError::NotFound)?;
+        let mut preset = revision.preset;
+        let current_preset = self.database_manager.get_preset(preset_id).await;
+        preset.is_default = current_preset
+            .as_ref()
+            .is_some_and(|current_preset| current_preset.is_default);
+        // The revision replaces whatever is stored now
+        preset.metadata.version = current_preset
+            .map(|current_preset| current_preset.metadata.version)
+            .unwrap_or_default();
+        let message = format!("Restored revision {}", revision_id);
+        self.save_preset(preset.clone(), Some(message)).await?;
+
+        let current_preset_id = { self.cache_manager.lock().unwrap().current_preset_id };
+        if current_preset_id == Some(preset_id) {
+            self.load_preset(preset_id).await;
+        }
+        Ok(preset)
+    }
+
+    /// Makes the setlist the active one and loads the preset at its stored position
+    pub async fn activate_setlist(&self, setlist_id: Uuid) -> Result<Preset, SetlistError> {
+        let setlist = self
+            .database_manager
+            .get_setlist(setlist_id)
+            .await
+            .ok_or(SetlistError::NotFound)?;
+        self.database_manager
+            .set_active_setlist_id(setlist_id)
+            .await?;
+        let position = setlist.position;
+        self.go_to_setlist_position(setlist, position).await
+    }
+
+    pub async fn move_in_active_setlist(
+        &self,
+        setlist_move: SetlistMove,
+    ) -> Result<Preset, SetlistError> {
+        let setlist = self
+            .database_manager
+            .get_active_setlist()
+            .await
+            .ok_or(SetlistError::NoActiveSetlist)?;
+        let position = match setlist_move {
+            SetlistMove::Next => setlist.position + 1,
+            SetlistMove::Previous => setlist
+                .position
+                .checked_sub(1)
+                .ok_or(SetlistError::PositionOutOfRange)?,
+            SetlistMove::Position(position) => position,
+        };
+        self.go_to_setlist_position(setlist, position).await
+    }
+
+    async fn go_to_setlist_position(
+        &self,
+        setlist: Setlist,
+        position: usize,
+    ) -> Result<Preset, SetlistError> {
+        let preset_id = *setlist
+            .preset_ids
+            .get(position)
+            .ok_or(SetlistError::PositionOutOfRange)?;
+        let preset = self
+            .load_preset(preset_id)
+            .await
+            .ok_or(SetlistError::PresetNotFound)?;
+        self.database_manager
+            .set_setlist_position(setlist.id, position)
+            .await?;
+        self.events_manager
+            .publish(StateEvent::SetlistPositionChanged {
+                setlist_id: setlist.id,
+                position,
+            });
+        Ok(preset)
+    }
+
+    pub fn get_scenes(&self) -> (Vec<Scene>, Option<usize>) {
+        let cache_manager = self.cache_manager.lock().unwrap();
+        (
+            cache_manager.current_scenes.clone(),
+            cache_manager.current_scene_index,
+        )
+    }
+
+    /// Applies the scene on top of the running chain, without rebuilding any processor
+    pub fn select_scene(&self, scene_index: usize) -> Result<(), SceneError> {
+        let scene = {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            let scene = cache_manager
+                .current_scenes
+                .get(scene_index)
+                .cloned()
+                .ok_or(SceneError::NotFound)?;
+            cache_manager.current_scene_index = Some(scene_index);
+            scene
+        };
+        let processors = {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            dsp_manager.apply_processors_overrides(&scene.processors);
+            dsp_manager.get_processors_info()
+        };
+        // The scene rewrites settings the recorded edits started from
+        self.history_manager.clear();
+        self.events_manager.publish(StateEvent::SceneSelected {
+            index: scene_index,
+            processors,
+        });
+        Ok(())
+    }
+
+    /// Stores the current state of the chain as a new scene and returns its index
+    pub fn create_scene(&self, name: String) -> usize {
+        let processors = self.dsp_manager.lock().unwrap().get_processors_info();
+        let index = {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            cache_manager
+                .current_scenes
+                .push(Scene { name, processors });
+            let index = cache_manager.current_scenes.len() - 1;
+            cache_manager.current_scene_index = Some(index);
+            index
+        };
+        self.publish_scenes();
+        index
+    }
+
+    /// Overwrites the scene with the current state of the chain
+    pub fn save_scene(&self, scene_index: usize) -> Result<(), SceneError> {
+        let processors = self.dsp_manager.lock().unwrap().get_processors_info();
+        self.transform_scene(scene_index, |scene| scene.processors = processors)
+    }
+
+    pub fn rename_scene(&self, scene_index: usize, name: String) -> Result<(), SceneError> {
+        self.transform_scene(scene_index, |scene| scene.name = name)
+    }
+
+    pub fn delete_scene(&self, scene_index: usize) -> Result<(), SceneError> {
+        {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            if scene_index >= cache_manager.current_scenes.len() {
+                return Err(SceneError::NotFound);
+            }
+            cache_manager.current_scenes.remove(scene_index);
+            cache_manager.current_scene_index = match cache_manager.current_scene_index {
+                Some(index) if index == scene_index => None,
+                Some(index) if index > scene_index => Some(index - 1),
+                other => other,
+            };
+        }
+        self.publish_scenes();
+        Ok(())
+    }
+
+    fn transform_scene<Function>(
+        &self,
+        scene_index: usize,
+        transform_function: Function,
+    ) -> Result<(), SceneError>
+    where
+        Function: FnOnce(&mut Scene),
+    {
+        {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            let scene = cache_manager
+                .current_scenes
+                .get_mut(scene_index)
+                .ok_or(SceneError::NotFound)?;
+            transform_function(scene);
+        }
+        self.publish_scenes();
+        Ok(())
+    }
+
+    fn publish_scenes(&self) {
+        let names = self
+            .cache_manager
+            .lock()
+            .unwrap()
+            .current_scenes
+            .iter()
+            .map(|scene| scene.name.clone())
+            .collect();
+        self.events_manager
+            .publish(StateEvent::ScenesChanged { names });
+    }
+}
+
+/// Milliseconds since the Unix epoch
+pub fn get_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,178 @@
+use crate::managers::database::models::Preset;
+use crate::managers::dsp::models::{ProcessorInfo, ProcessorType};
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+static NUMERIC_TOLERANCE: f32 = 1e-4;
+
+#[derive(Serialize, Deserialize)]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ParameterChange {
+    pub parameter: Parameter,
+    pub from: Option<ParameterValue>,
+    pub to: Option<ParameterValue>,
+}
+
+/// Processors are compared by position in the chain
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessorDiff {
+    Added {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    Removed {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    Replaced {
+        index: usize,
+        change: Change<ProcessorType>,
+    },
+    Changed {
+        index: usize,
+        processor_type: ProcessorType,
+        bypassed: Option<Change<bool>>,
+        parameters: Vec<ParameterChange>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PresetDiff {
+    pub name: Option<Change<String>>,
+    pub tempo: Option<Change<f32>>,
+    pub processors: Vec<ProcessorDiff>,
+    pub are_scenes_changed: bool,
+    pub are_expression_assignments_changed: bool,
+}
+
+impl PresetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.tempo.is_none()
+            && self.processors.is_empty()
+            && !self.are_scenes_changed
+            && !self.are_expression_assignments_changed
+    }
+}
+
+pub fn diff_presets(from: &Preset, to: &Preset) -> PresetDiff {
+    let processors_count = from.processors.len().max(to.processors.len());
+    let processors = (0..processors_count)
+        .filter_map(
+            |index| match (from.processors.get(index), to.processors.get(index)) {
+                (Some(from), Some(to)) => diff_processors(index, from, to),
+                (None, Some(to)) => Some(ProcessorDiff::Added {
+                    index,
+                    processor_type: to.processor_type.clone(),
+                }),
+                (Some(from), None) => Some(ProcessorDiff::Removed {
+                    index,
+                    processor_type: from.processor_type.clone(),
+                }),
+                (None, None) => None,
+            },
+        )
+        .collect();
+
+    PresetDiff {
+        name: get_change(&from.name, &to.name),
+        tempo: get_change(&from.tempo, &to.tempo),
+        processors,
+        are_scenes_changed: serde_json::to_value(&from.scenes).ok()
+            != serde_json::to_value(&to.scenes).ok(),
+        are_expression_assignments_changed: serde_json::to_value(&from.expression_assignments).ok()
+            != serde_json::to_value(&to.expression_assignments).ok(),
+    }
+}
+
+fn diff_processors(
+    index: usize,
+    from: &ProcessorInfo,
+    to: &ProcessorInfo,
+) -> Option<ProcessorDiff> {
+    if from.processor_type != to.processor_type {
+        return Some(ProcessorDiff::Replaced {
+            index,
+            change: Change {
+                from: from.processor_type.clone(),
+                to: to.processor_type.clone(),
+            },
+        });
+    }
+
+    let bypassed = get_change(&from.settings.bypassed, &to.settings.bypassed);
+    let from_values = get_parameter_values(from);
+    let to_values = get_parameter_values(to);
+    let parameters: Vec<ParameterChange> = Parameter::iter()
+        .filter_map(|parameter| {
+            let from = find_parameter_value(&from_values, parameter);
+            let to = find_parameter_value(&to_values, parameter);
+            (!are_values_equal(&from, &to)).then_some(ParameterChange {
+                parameter,
+                from,
+                to,
+            })
+        })
+        .collect();
+
+    if bypassed.is_none() && parameters.is_empty() {
+        None
+    } else {
+        Some(ProcessorDiff::Changed {
+            index,
+            processor_type: to.processor_type.clone(),
+            bypassed,
+            parameters,
+        })
+    }
+}
+
+/// Numeric values go through the processors and back, so tiny rounding errors are ignored
+fn are_values_equal(from: &Option<ParameterValue>, to: &Option<ParameterValue>) -> bool {
+    match (from, to) {
+        (Some(ParameterValue::Numeric(from)), Some(ParameterValue::Numeric(to))) => {
+            (from - to).abs() < NUMERIC_TOLERANCE
+        }
+        _ => from == to,
+    }
+}
+
+fn get_change<T: PartialEq + Clone>(from: &T, to: &T) -> Option<Change<T>> {
+    (from != to).then(|| Change {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+fn get_parameter_values(processor: &ProcessorInfo) -> Vec<(Parameter, ParameterValue)> {
+    processor
+        .parameters
+        .numeric
+        .iter()
+        .map(|(parameter, value)| (*parameter, ParameterValue::Numeric(*value)))
+        .chain(
+            processor
+                .parameters
+                .string
+                .iter()
+                .map(|(parameter, value)| (*parameter, ParameterValue::String(value.clone()))),
+        )
+        .collect()
+}
+
+fn find_parameter_value(
+    values: &[(Parameter, ParameterValue)],
+    parameter: Parameter,
+) -> Option<ParameterValue> {
+    values
+        .iter()
+        .find(|(current, _)| *current == parameter)
+        .map(|(_, value)| value.clone())
+}
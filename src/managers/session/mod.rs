@@ -0,0 +1,156 @@
+use crate::managers::audio_device_settings::AudioDeviceSettingsManager;
+use crate::managers::cache::CacheManager;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::file_system::FileSystemManager;
+use crate::managers::session::models::{DeviceSession, Session};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub mod models;
+
+static SESSION_FILE_NAME: &str = "session.json";
+
+/// Persists the live state of the rig, so that it can be resumed at the next start
+#[derive(Clone)]
+pub struct SessionManager {
+    audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+    cache_manager: Arc<Mutex<CacheManager>>,
+    dsp_manager: Arc<Mutex<DSPManager>>,
+    events_manager: EventsManager,
+    file_system_manager: FileSystemManager,
+    last_saved_content: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SessionManager {
+    pub fn new(
+        audio_device_settings_manager: Option<Arc<AudioDeviceSettingsManager>>,
+        cache_manager: Arc<Mutex<CacheManager>>,
+        dsp_manager: Arc<Mutex<DSPManager>>,
+        events_manager: EventsManager,
+        file_system_manager: FileSystemManager,
+    ) -> Self {
+        Self {
+            audio_device_settings_manager,
+            cache_manager,
+            dsp_manager,
+            events_manager,
+            file_system_manager,
+            last_saved_content: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Returns `false` when there is no valid session to resume
+    pub fn restore(&self) -> bool {
+        let Ok(content) = self.file_system_manager.read_file(SESSION_FILE_NAME) else {
+            return false;
+        };
+        let session: Session = match serde_json::from_slice(&content) {
+            Ok(session) => session,
+            Err(error) => {
+                tracing::warn!("cannot read the last session, ignoring it: {}", error);
+                return false;
+            }
+        };
+
+        {
+            let mut cache_manager = self.cache_manager.lock().unwrap();
+            cache_manager.current_preset_id = session.preset_id;
+            cache_manager.current_scenes = session.scenes;
+            cache_manager.current_scene_index = session.scene_index;
+        }
+        {
+            let mut dsp_manager = self.dsp_manager.lock().unwrap();
+            dsp_manager.load_processors(&session.processors);
+            dsp_manager.set_expression_assignments(&session.expression_assignments);
+            let _ = dsp_manager.set_tempo(session.tempo);
+        }
+        if let (Some(audio_device_settings_manager), Some(device)) =
+            (&self.audio_device_settings_manager, session.device)
+        {
+            let _ = audio_device_settings_manager.set_input_gain(device.input_gain);
+            let _ = audio_device_settings_manager.set_volume(device.volume);
+            let _ = audio_device_settings_manager.set_mic_bias(device.mic_bias);
+        }
+        if let Some(id) = session.preset_id {
+            self.events_manager.publish(StateEvent::PresetLoaded {
+                id,
+                processors: session.processors,
+            });
+        }
+        *self.last_saved_content.lock().unwrap() = content;
+
+        tracing::info!("last session restored");
+        true
+    }
+
+    /// Writes the session file, unless nothing changed since the last write
+    pub fn save(&self) {
+        let session = self.get_session();
+        let Ok(content) = serde_json::to_vec(&session) else {
+            return;
+        };
+        let mut last_saved_content = self.last_saved_content.lock().unwrap();
+        if *last_saved_content == content {
+            return;
+        }
+        match self
+            .file_system_manager
+            .write_file_atomically(SESSION_FILE_NAME, &content)
+        {
+            Ok(_) => *last_saved_content = content,
+            Err(error) => tracing::error!("cannot save the session: {:?}", error),
+        }
+    }
+
+    pub fn start_autosave(&self, interval: Duration) {
+        let session_manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                session_manager.save();
+            }
+        });
+    }
+
+    fn get_session(&self) -> Session {
+        let (preset_id, scenes, scene_index) = {
+            let cache_manager = self.cache_manager.lock().unwrap();
+            (
+                cache_manager.current_preset_id,
+                cache_manager.current_scenes.clone(),
+                cache_manager.current_scene_index,
+            )
+        };
+        let (processors, expression_assignments, tempo) = {
+            let dsp_manager = self.dsp_manager.lock().unwrap();
+            (
+                dsp_manager.get_processors_info(),
+                dsp_manager.get_expression_assignments(),
+                dsp_manager.get_tempo(),
+            )
+        };
+        let device =
+            self.audio_device_settings_manager
+                .as_ref()
+                .and_then(|audio_device_settings_manager| {
+                    Some(DeviceSession {
+                        input_gain: audio_device_settings_manager.get_input_gain().ok()?,
+                        volume: audio_device_settings_manager.get_volume().ok()?,
+                        mic_bias: audio_device_settings_manager.is_mic_bias_on().ok()?,
+                    })
+                });
+
+        Session {
+            preset_id,
+            processors,
+            scenes,
+            scene_index,
+            expression_assignments,
+            tempo,
+            device,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::managers::database::models::Scene;
+use crate::managers::dsp::models::{ExpressionAssignment, ProcessorInfo};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Everything needed to bring the rig back after a power cycle, saved or not
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    pub preset_id: Option<Uuid>,
+    pub processors: Vec<ProcessorInfo>,
+    pub scenes: Vec<Scene>,
+    pub scene_index: Option<usize>,
+    #[serde(default)]
+    pub expression_assignments: Vec<ExpressionAssignment>,
+    pub tempo: f32,
+    pub device: Option<DeviceSession>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeviceSession {
+    pub input_gain: f32,
+    pub volume: f32,
+    pub mic_bias: bool,
+}
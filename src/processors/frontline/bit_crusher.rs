@@ -0,0 +1,105 @@
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::FrontlineProcessor;
+use crate::processors::internal::noise::Noise;
+use crate::processors::{ProcessingContext, Processor};
+
+static MIN_BIT_DEPTH: f32 = 1.0;
+static MAX_BIT_DEPTH: f32 = 16.0;
+static MAX_DOWNSAMPLE_FACTOR: f32 = 64.0;
+
+pub struct BitCrusher {
+    // Parameters
+    bit_depth: f32,
+    downsample: f32,
+    dither: bool,
+    mix: f32,
+
+    // State
+    noise: Noise,
+    quantization_levels: f32,
+    hold_length: f32,
+    hold_counter: f32,
+    held_sample: f32,
+}
+
+impl BitCrusher {
+    fn set_bit_depth(&mut self, bit_depth: f32) {
+        self.bit_depth = bit_depth.clamp(0.0, 1.0);
+        let bits = MIN_BIT_DEPTH + self.bit_depth * (MAX_BIT_DEPTH - MIN_BIT_DEPTH);
+        self.quantization_levels = 2.0_f32.powf(bits - 1.0);
+    }
+
+    fn set_downsample(&mut self, downsample: f32) {
+        self.downsample = downsample.clamp(0.0, 1.0);
+        self.hold_length = 1.0 + self.downsample * (MAX_DOWNSAMPLE_FACTOR - 1.0);
+    }
+}
+
+impl Processor for BitCrusher {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self {
+        let mut processor = Self {
+            bit_depth: 0.0,
+            downsample: 0.0,
+            dither: false,
+            mix: 1.0,
+            noise: Noise::new(sample_rate, block_size),
+            quantization_levels: 1.0,
+            hold_length: 1.0,
+            hold_counter: 0.0,
+            held_sample: 0.0,
+        };
+        processor.set_bit_depth(0.5);
+        processor.set_downsample(0.1);
+        processor
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            // Sample-and-hold: a new input is only captured every `hold_length` samples
+            self.hold_counter += 1.0;
+            if self.hold_counter >= self.hold_length {
+                self.hold_counter -= self.hold_length;
+                self.held_sample = *sample;
+            }
+
+            let mut crushed_sample = self.held_sample * self.quantization_levels;
+            if self.dither {
+                crushed_sample += self.noise.next_triangular_sample();
+            }
+            crushed_sample = crushed_sample.round() / self.quantization_levels;
+
+            *sample = *sample * (1.0 - self.mix) + crushed_sample.clamp(-1.0, 1.0) * self.mix;
+        }
+    }
+}
+
+impl FrontlineProcessor for BitCrusher {
+    fn get_parameter(&self, parameter: Parameter) -> Option<ParameterValue> {
+        match parameter {
+            Parameter::BitDepth => Some(self.bit_depth.into()),
+            Parameter::Downsample => Some(self.downsample.into()),
+            Parameter::Dither => Some(if self.dither { 1.0 } else { 0.0 }.into()),
+            Parameter::Mix => Some(self.mix.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, parameter: Parameter, value: ParameterValue) {
+        match (parameter, value) {
+            (Parameter::BitDepth, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_bit_depth(wrapped_value)
+            }
+            (Parameter::Downsample, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_downsample(wrapped_value)
+            }
+            (Parameter::Dither, ParameterValue::Numeric(wrapped_value)) => {
+                self.dither = wrapped_value > 0.5
+            }
+            (Parameter::Mix, ParameterValue::Numeric(wrapped_value)) => {
+                self.mix = wrapped_value.clamp(0.0, 1.0)
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::processors::frontline::models::{Parameter, ParameterValue};
 use crate::processors::frontline::FrontlineProcessor;
 use crate::processors::internal::gain::Gain;
-use crate::processors::Processor;
+use crate::processors::{ProcessingContext, Processor};
 
 pub struct CleanBooster {
     drive: Gain,
@@ -16,8 +16,8 @@ impl Processor for CleanBooster {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
-        self.drive.process(data)
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
+        self.drive.process(data, context)
     }
 }
 
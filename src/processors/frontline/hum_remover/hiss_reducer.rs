@@ -0,0 +1,283 @@
+use crate::processors::frontline::models::NoiseProfile;
+use crate::processors::{ProcessingContext, Processor};
+use realfft::num_complex::Complex;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+const FRAME_SIZE: usize = 1024;
+const HOP_SIZE: usize = FRAME_SIZE / 2;
+const LATENCY: usize = FRAME_SIZE - HOP_SIZE;
+const LEARNING_TIME_SECONDS: f32 = 1.0;
+const OVER_SUBTRACTION: f32 = 2.0;
+const MIN_GAIN: f32 = 0.1;
+const GAIN_SMOOTHING: f32 = 0.6;
+const RAMP_TIME_SECONDS: f32 = 0.02;
+
+/// Spectral subtraction on a 50% overlapped STFT with square-root Hann windows. The noise profile
+/// is the average magnitude spectrum measured while learning.
+/// At zero amount the STFT is bypassed, so the reducer adds no latency until it is turned up:
+/// it then crossfades from the dry signal to the delayed reduced one, and back when turned down.
+pub struct HissReducer {
+    pub amount: f32,
+    forward_fft: Arc<dyn RealToComplex<f32>>,
+    inverse_fft: Arc<dyn ComplexToReal<f32>>,
+    window: Vec<f32>,
+    input_fifo: Vec<f32>,
+    output_fifo: Vec<f32>,
+    output_accumulator: Vec<f32>,
+    frame: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    forward_scratch: Vec<Complex<f32>>,
+    inverse_scratch: Vec<Complex<f32>>,
+    gains: Vec<f32>,
+    noise_profile: Vec<f32>,
+    has_noise_profile: bool,
+    learning_frames: usize,
+    learning_frames_remaining: usize,
+    position: usize,
+    sample_rate: u32,
+    is_running: bool,
+    priming_samples_remaining: usize,
+    wet_mix: f32,
+    ramp_step: f32,
+}
+
+impl HissReducer {
+    pub fn learn_noise_profile(&mut self) {
+        self.noise_profile.fill(0.0);
+        self.has_noise_profile = false;
+        self.learning_frames_remaining = self.learning_frames;
+    }
+
+    pub fn is_learning(&self) -> bool {
+        self.learning_frames_remaining > 0
+    }
+
+    pub fn get_noise_profile(&self) -> Option<NoiseProfile> {
+        self.has_noise_profile.then(|| NoiseProfile {
+            sample_rate: self.sample_rate,
+            magnitudes: self.noise_profile.clone(),
+        })
+    }
+
+    /// Bins are spaced on the sample rate, so a profile learned at another rate is
+    /// interpolated at the frequencies of this one
+    pub fn set_noise_profile(&mut self, noise_profile: &NoiseProfile) {
+        let Some(last_index) = noise_profile.magnitudes.len().checked_sub(1) else {
+            return;
+        };
+        let ratio = self.sample_rate as f32 / noise_profile.sample_rate as f32;
+        for (index, noise_bin) in self.noise_profile.iter_mut().enumerate() {
+            let position = index as f32 * ratio;
+            let lower_index = position as usize;
+            *noise_bin = if lower_index >= last_index {
+                noise_profile.magnitudes[last_index]
+            } else {
+                let fraction = position - lower_index as f32;
+                noise_profile.magnitudes[lower_index] * (1.0 - fraction)
+                    + noise_profile.magnitudes[lower_index + 1] * fraction
+            };
+        }
+        self.has_noise_profile = true;
+        self.learning_frames_remaining = 0;
+    }
+
+    /// Starts the STFT from silence, so that no frame from a previous run is replayed
+    fn start(&mut self) {
+        self.input_fifo.fill(0.0);
+        self.output_fifo.fill(0.0);
+        self.output_accumulator.fill(0.0);
+        self.gains.fill(1.0);
+        self.position = LATENCY;
+        self.priming_samples_remaining = LATENCY;
+        self.is_running = true;
+    }
+
+    fn process_frame(&mut self) {
+        for ((frame_sample, input_sample), window_sample) in self
+            .frame
+            .iter_mut()
+            .zip(&self.input_fifo)
+            .zip(&self.window)
+        {
+            *frame_sample = input_sample * window_sample;
+        }
+        let _ = self.forward_fft.process_with_scratch(
+            &mut self.frame,
+            &mut self.spectrum,
+            &mut self.forward_scratch,
+        );
+
+        if self.is_learning() {
+            for (noise_bin, bin) in self.noise_profile.iter_mut().zip(&self.spectrum) {
+                *noise_bin += bin.norm() / self.learning_frames as f32;
+            }
+            self.learning_frames_remaining -= 1;
+            if !self.is_learning() {
+                self.has_noise_profile = true;
+                tracing::info!("hiss reducer learned a new noise profile");
+            }
+        }
+
+        if self.has_noise_profile {
+            let min_gain = 1.0 - self.amount * (1.0 - MIN_GAIN);
+            for ((bin, gain), noise_bin) in self
+                .spectrum
+                .iter_mut()
+                .zip(self.gains.iter_mut())
+                .zip(&self.noise_profile)
+            {
+                let magnitude = bin.norm().max(f32::EPSILON);
+                let target_gain =
+                    (1.0 - self.amount * OVER_SUBTRACTION * noise_bin / magnitude).max(min_gain);
+                *gain = GAIN_SMOOTHING * *gain + (1.0 - GAIN_SMOOTHING) * target_gain;
+                *bin *= *gain;
+            }
+        }
+
+        // The inverse transform requires purely real DC and Nyquist bins
+        if let Some(bin) = self.spectrum.first_mut() {
+            bin.im = 0.0;
+        }
+        if let Some(bin) = self.spectrum.last_mut() {
+            bin.im = 0.0;
+        }
+        let _ = self.inverse_fft.process_with_scratch(
+            &mut self.spectrum,
+            &mut self.frame,
+            &mut self.inverse_scratch,
+        );
+
+        for ((accumulated_sample, frame_sample), window_sample) in self
+            .output_accumulator
+            .iter_mut()
+            .zip(&self.frame)
+            .zip(&self.window)
+        {
+            *accumulated_sample += frame_sample * window_sample / FRAME_SIZE as f32;
+        }
+        self.output_fifo[..HOP_SIZE].copy_from_slice(&self.output_accumulator[..HOP_SIZE]);
+        self.output_accumulator.copy_within(HOP_SIZE.., 0);
+        self.output_accumulator[FRAME_SIZE - HOP_SIZE..].fill(0.0);
+        self.input_fifo.copy_within(HOP_SIZE.., 0);
+    }
+}
+
+impl Processor for HissReducer {
+    fn new(sample_rate: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let forward_fft = planner.plan_fft_forward(FRAME_SIZE);
+        let inverse_fft = planner.plan_fft_inverse(FRAME_SIZE);
+        let window = (0..FRAME_SIZE)
+            .map(|index| (0.5 * (1.0 - (TAU * index as f32 / FRAME_SIZE as f32).cos())).sqrt())
+            .collect();
+        let bins_count = FRAME_SIZE / 2 + 1;
+        let learning_frames =
+            ((LEARNING_TIME_SECONDS * *sample_rate as f32) as usize / HOP_SIZE).max(1);
+
+        Self {
+            amount: 0.0,
+            spectrum: forward_fft.make_output_vec(),
+            forward_scratch: forward_fft.make_scratch_vec(),
+            inverse_scratch: inverse_fft.make_scratch_vec(),
+            forward_fft,
+            inverse_fft,
+            window,
+            input_fifo: vec![0.0; FRAME_SIZE],
+            output_fifo: vec![0.0; HOP_SIZE],
+            output_accumulator: vec![0.0; FRAME_SIZE],
+            frame: vec![0.0; FRAME_SIZE],
+            gains: vec![1.0; bins_count],
+            noise_profile: vec![0.0; bins_count],
+            has_noise_profile: false,
+            learning_frames,
+            learning_frames_remaining: 0,
+            position: LATENCY,
+            sample_rate: *sample_rate,
+            is_running: false,
+            priming_samples_remaining: 0,
+            wet_mix: 0.0,
+            ramp_step: 1.0 / (RAMP_TIME_SECONDS * *sample_rate as f32).max(1.0),
+        }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        let target_wet_mix = if self.amount > 0.0 { 1.0 } else { 0.0 };
+        if !self.is_running {
+            if target_wet_mix == 0.0 && !self.is_learning() {
+                return;
+            }
+            self.start();
+        }
+
+        for sample in data.iter_mut() {
+            let dry_sample = *sample;
+            self.input_fifo[self.position] = dry_sample;
+            let wet_sample = self.output_fifo[self.position - LATENCY];
+            self.position += 1;
+            if self.position >= FRAME_SIZE {
+                self.position = LATENCY;
+                self.process_frame();
+            }
+
+            // The wet signal only fades in once the first frames have come out
+            if self.priming_samples_remaining > 0 {
+                self.priming_samples_remaining -= 1;
+            } else if self.wet_mix < target_wet_mix {
+                self.wet_mix = (self.wet_mix + self.ramp_step).min(target_wet_mix);
+            } else if self.wet_mix > target_wet_mix {
+                self.wet_mix = (self.wet_mix - self.ramp_step).max(target_wet_mix);
+            }
+            *sample = dry_sample + (wet_sample - dry_sample) * self.wet_mix;
+        }
+
+        if self.wet_mix == 0.0 && target_wet_mix == 0.0 && !self.is_learning() {
+            self.is_running = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_amount_passes_the_signal_through_without_latency() {
+        let mut hiss_reducer = HissReducer::new(&48000, &128);
+        let context = ProcessingContext { tempo: 120.0 };
+        let input: Vec<f32> = (0..128).map(|index| (index as f32 * 0.1).sin()).collect();
+        let mut data = input.clone();
+
+        hiss_reducer.process(&mut data, &context);
+
+        assert_eq!(data, input);
+    }
+
+    #[test]
+    fn turning_the_amount_down_returns_to_the_dry_signal() {
+        let mut hiss_reducer = HissReducer::new(&48000, &128);
+        let context = ProcessingContext { tempo: 120.0 };
+        let input: Vec<f32> = (0..128).map(|index| (index as f32 * 0.1).sin()).collect();
+
+        hiss_reducer.amount = 1.0;
+        for _ in 0..40 {
+            hiss_reducer.process(&mut input.clone(), &context);
+        }
+        assert_eq!(hiss_reducer.wet_mix, 1.0);
+
+        hiss_reducer.amount = 0.0;
+        for _ in 0..40 {
+            hiss_reducer.process(&mut input.clone(), &context);
+        }
+        let mut data = input.clone();
+        hiss_reducer.process(&mut data, &context);
+
+        assert!(!hiss_reducer.is_running);
+        assert_eq!(data, input);
+    }
+}
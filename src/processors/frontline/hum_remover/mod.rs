@@ -0,0 +1,152 @@
+use crate::processors::frontline::hum_remover::hiss_reducer::HissReducer;
+use crate::processors::frontline::hum_remover::models::MainsFrequency;
+use crate::processors::frontline::hum_remover::tracker::MainsTracker;
+use crate::processors::frontline::models::{NoiseProfile, Parameter, ParameterValue};
+use crate::processors::frontline::FrontlineProcessor;
+use crate::processors::internal::filter::models::SecondOrderFilterType;
+use crate::processors::internal::filter::Filter;
+use crate::processors::{ProcessingContext, Processor};
+
+mod hiss_reducer;
+mod models;
+mod tracker;
+
+const MAX_HARMONICS: usize = 8;
+const NOTCH_BANDWIDTH: f32 = 2.0;
+const RETUNE_THRESHOLD: f32 = 0.01;
+
+pub struct HumRemover {
+    // Parameters
+    mains_frequency: MainsFrequency,
+    harmonics: f32,
+    tracking: bool,
+
+    // Chain
+    tracker: MainsTracker,
+    notches: Vec<Filter>,
+    active_notches_count: usize,
+    tuned_frequency: f32,
+    hiss_reducer: HissReducer,
+    sample_rate: f32,
+}
+
+impl HumRemover {
+    fn set_mains_frequency(&mut self, mains_frequency: MainsFrequency) {
+        self.mains_frequency = mains_frequency;
+        self.tracker
+            .set_nominal_frequency(mains_frequency.get_frequency());
+        self.tune_notches(mains_frequency.get_frequency());
+    }
+
+    fn set_harmonics(&mut self, harmonics: f32) {
+        self.harmonics = harmonics.clamp(0.0, 1.0);
+        self.active_notches_count =
+            1 + (self.harmonics * (MAX_HARMONICS - 1) as f32).round() as usize;
+    }
+
+    fn set_tracking(&mut self, tracking: bool) {
+        self.tracking = tracking;
+        if !tracking {
+            self.set_mains_frequency(self.mains_frequency);
+        }
+    }
+
+    fn tune_notches(&mut self, frequency: f32) {
+        self.tuned_frequency = frequency;
+        for (index, notch) in self.notches.iter_mut().enumerate() {
+            let harmonic_frequency = (frequency * (index + 1) as f32).min(0.45 * self.sample_rate);
+            notch.make_second_order(
+                SecondOrderFilterType::Notch,
+                harmonic_frequency,
+                harmonic_frequency / NOTCH_BANDWIDTH,
+            );
+        }
+    }
+}
+
+impl Processor for HumRemover {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self {
+        let mut processor = Self {
+            mains_frequency: MainsFrequency::Fifty,
+            harmonics: 0.0,
+            tracking: true,
+            tracker: MainsTracker::new(sample_rate, block_size),
+            notches: (0..MAX_HARMONICS)
+                .map(|_| Filter::new(sample_rate, block_size))
+                .collect(),
+            active_notches_count: 1,
+            tuned_frequency: 0.0,
+            hiss_reducer: HissReducer::new(sample_rate, block_size),
+            sample_rate: *sample_rate as f32,
+        };
+        processor.set_mains_frequency(MainsFrequency::Fifty);
+        processor.set_harmonics(3.0 / (MAX_HARMONICS - 1) as f32);
+        processor
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
+        if self.tracking {
+            self.tracker.process(data, context);
+            let tracked_frequency = self.tracker.get_frequency();
+            if (tracked_frequency - self.tuned_frequency).abs() > RETUNE_THRESHOLD {
+                self.tune_notches(tracked_frequency);
+            }
+        }
+
+        for notch in self.notches.iter_mut().take(self.active_notches_count) {
+            notch.process(data, context);
+        }
+
+        self.hiss_reducer.process(data, context);
+    }
+}
+
+impl FrontlineProcessor for HumRemover {
+    fn get_parameter(&self, parameter: Parameter) -> Option<ParameterValue> {
+        match parameter {
+            Parameter::BaseFrequency => Some(match self.mains_frequency {
+                MainsFrequency::Fifty => 0.0.into(),
+                MainsFrequency::Sixty => 1.0.into(),
+            }),
+            Parameter::Harmonics => Some(self.harmonics.into()),
+            Parameter::Tracking => Some(if self.tracking { 1.0 } else { 0.0 }.into()),
+            Parameter::HissReduction => Some(self.hiss_reducer.amount.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, parameter: Parameter, value: ParameterValue) {
+        match (parameter, value) {
+            (Parameter::BaseFrequency, ParameterValue::Numeric(0.0..=0.5)) => {
+                self.set_mains_frequency(MainsFrequency::Fifty)
+            }
+            (Parameter::BaseFrequency, ParameterValue::Numeric(0.5..=1.0)) => {
+                self.set_mains_frequency(MainsFrequency::Sixty)
+            }
+            (Parameter::Harmonics, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_harmonics(wrapped_value)
+            }
+            (Parameter::Tracking, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_tracking(wrapped_value > 0.5)
+            }
+            (Parameter::HissReduction, ParameterValue::Numeric(wrapped_value)) => {
+                self.hiss_reducer.amount = wrapped_value.clamp(0.0, 1.0)
+            }
+            _ => {}
+        }
+    }
+
+    fn learn_noise_profile(&mut self) -> bool {
+        self.hiss_reducer.learn_noise_profile();
+        true
+    }
+
+    fn get_noise_profile(&self) -> Option<NoiseProfile> {
+        self.hiss_reducer.get_noise_profile()
+    }
+
+    fn set_noise_profile(&mut self, noise_profile: &NoiseProfile) {
+        self.hiss_reducer.set_noise_profile(noise_profile);
+    }
+}
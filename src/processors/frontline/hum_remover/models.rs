@@ -0,0 +1,14 @@
+#[derive(Clone, Copy)]
+pub enum MainsFrequency {
+    Fifty,
+    Sixty,
+}
+
+impl MainsFrequency {
+    pub fn get_frequency(&self) -> f32 {
+        match self {
+            MainsFrequency::Fifty => 50.0,
+            MainsFrequency::Sixty => 60.0,
+        }
+    }
+}
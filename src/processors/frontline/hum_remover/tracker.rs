@@ -0,0 +1,124 @@
+use crate::processors::{ProcessingContext, Processor};
+use std::f64::consts::TAU;
+
+// Mains frequency is tightly regulated, so the estimate never leaves this range
+const MAX_DEVIATION: f32 = 1.0;
+const PROBE_SPACING: f32 = 0.25;
+const MIN_HUM_AMPLITUDE: f64 = 1e-4;
+
+struct Goertzel {
+    coefficient: f64,
+    state: f64,
+    previous_state: f64,
+}
+
+impl Goertzel {
+    fn tune(&mut self, frequency: f32, sample_rate: f32) {
+        self.coefficient = 2.0 * (TAU * frequency as f64 / sample_rate as f64).cos();
+        self.state = 0.0;
+        self.previous_state = 0.0;
+    }
+
+    #[inline(always)]
+    fn push(&mut self, sample: f64) {
+        let state = sample + self.coefficient * self.state - self.previous_state;
+        self.previous_state = self.state;
+        self.state = state;
+    }
+
+    fn get_power(&self) -> f64 {
+        self.state.powi(2) + self.previous_state.powi(2)
+            - self.coefficient * self.state * self.previous_state
+    }
+}
+
+/// Estimates the exact mains frequency by probing the spectrum slightly below, at and slightly
+/// above the current estimate and moving towards the strongest side once per window.
+pub struct MainsTracker {
+    nominal_frequency: f32,
+    frequency: f32,
+    probes: [Goertzel; 3],
+    window_length: usize,
+    samples_in_window: usize,
+    sample_rate: f32,
+}
+
+impl MainsTracker {
+    pub fn set_nominal_frequency(&mut self, nominal_frequency: f32) {
+        self.nominal_frequency = nominal_frequency;
+        self.frequency = nominal_frequency;
+        self.tune_probes();
+    }
+
+    pub fn get_frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    fn tune_probes(&mut self) {
+        for (index, probe) in self.probes.iter_mut().enumerate() {
+            let offset = (index as f32 - 1.0) * PROBE_SPACING;
+            probe.tune(self.frequency + offset, self.sample_rate);
+        }
+        self.samples_in_window = 0;
+    }
+
+    fn update_estimate(&mut self) {
+        let normalisation = (self.window_length as f64 / 2.0).powi(2);
+        let powers = self
+            .probes
+            .each_ref()
+            .map(|probe| probe.get_power() / normalisation);
+
+        // Parabolic interpolation of the log-power around the central probe
+        let [lower, centre, upper] = powers.map(|power| (power + f64::MIN_POSITIVE).ln());
+        let curvature = lower - 2.0 * centre + upper;
+        if powers[1] > MIN_HUM_AMPLITUDE.powi(2) && curvature < 0.0 {
+            let offset = (0.5 * (lower - upper) / curvature) as f32 * PROBE_SPACING;
+            let estimate = self.frequency + offset.clamp(-PROBE_SPACING, PROBE_SPACING);
+            self.frequency = (0.5 * (self.frequency + estimate)).clamp(
+                self.nominal_frequency - MAX_DEVIATION,
+                self.nominal_frequency + MAX_DEVIATION,
+            );
+        }
+
+        self.tune_probes();
+    }
+}
+
+impl Processor for MainsTracker {
+    fn new(sample_rate: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        let probe = || Goertzel {
+            coefficient: 0.0,
+            state: 0.0,
+            previous_state: 0.0,
+        };
+        let mut tracker = Self {
+            nominal_frequency: 50.0,
+            frequency: 50.0,
+            probes: [probe(), probe(), probe()],
+            // One second windows resolve the probes well enough to tell them apart
+            window_length: *sample_rate as usize,
+            samples_in_window: 0,
+            sample_rate: *sample_rate as f32,
+        };
+        tracker.tune_probes();
+        tracker
+    }
+
+    /// Analyses the block without modifying it
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter() {
+            for probe in self.probes.iter_mut() {
+                probe.push(*sample as f64);
+            }
+            self.samples_in_window += 1;
+            if self.samples_in_window >= self.window_length {
+                self.update_estimate();
+            }
+        }
+    }
+}
@@ -1,18 +1,28 @@
 use crate::managers::dsp::models::ProcessorType;
+use crate::processors::frontline::bit_crusher::BitCrusher;
 use crate::processors::frontline::clean_booster::CleanBooster;
-use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::hum_remover::HumRemover;
+use crate::processors::frontline::models::{NoiseProfile, Parameter, ParameterValue};
 use crate::processors::frontline::ocd::Ocd;
+use crate::processors::frontline::ring_modulator::RingModulator;
+use crate::processors::frontline::rotary::Rotary;
 use crate::processors::frontline::simple_distortion::SimpleDistortion;
+use crate::processors::frontline::volume_pedal::VolumePedal;
 use crate::processors::Processor;
 use regex::Regex;
 use std::any::type_name_of_val;
 use std::collections::HashMap;
 use strum::IntoEnumIterator;
 
+pub mod bit_crusher;
 pub mod clean_booster;
+pub mod hum_remover;
 pub mod models;
 pub mod ocd;
+pub mod ring_modulator;
+pub mod rotary;
 pub mod simple_distortion;
+pub mod volume_pedal;
 
 pub type BoxedProcessor = Box<dyn FrontlineProcessor + Send>;
 
@@ -46,6 +56,18 @@ pub trait FrontlineProcessor: Processor {
             .collect()
     }
 
+    /// Starts learning a new noise profile, returning `false` if the processor doesn't use one
+    fn learn_noise_profile(&mut self) -> bool {
+        false
+    }
+
+    /// The learned profile, which is not a parameter but has to survive re-creating the processor
+    fn get_noise_profile(&self) -> Option<NoiseProfile> {
+        None
+    }
+
+    fn set_noise_profile(&mut self, _noise_profile: &NoiseProfile) {}
+
     fn get_type(&self) -> ProcessorType {
         let object_type = type_name_of_val(self);
         let regex = Regex::new(r"^.*::").unwrap();
@@ -53,6 +75,11 @@ pub trait FrontlineProcessor: Processor {
     }
 }
 
+/// Types `create_processor_from_type` can build
+pub fn is_processor_type_supported(processor_type: &ProcessorType) -> bool {
+    !matches!(processor_type, ProcessorType::Clone | ProcessorType::IR)
+}
+
 pub fn create_processor_from_type(
     processor_type: &ProcessorType,
     sample_rate: &u32,
@@ -64,6 +91,11 @@ pub fn create_processor_from_type(
         }
         ProcessorType::CleanBooster => Box::new(CleanBooster::new(sample_rate, buffer_size)),
         ProcessorType::Ocd => Box::new(Ocd::new(sample_rate, buffer_size)),
+        ProcessorType::BitCrusher => Box::new(BitCrusher::new(sample_rate, buffer_size)),
+        ProcessorType::RingModulator => Box::new(RingModulator::new(sample_rate, buffer_size)),
+        ProcessorType::VolumePedal => Box::new(VolumePedal::new(sample_rate, buffer_size)),
+        ProcessorType::Rotary => Box::new(Rotary::new(sample_rate, buffer_size)),
+        ProcessorType::HumRemover => Box::new(HumRemover::new(sample_rate, buffer_size)),
         ProcessorType::Clone => todo!("Implement CloneProcessor"),
         ProcessorType::IR => todo!("Implement IRProcessor"),
     }
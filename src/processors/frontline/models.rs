@@ -9,15 +9,41 @@ pub enum Parameter {
     FilePath,
     Tone,
     FilterSwitch,
+    BitDepth,
+    Downsample,
+    Dither,
+    Mix,
+    Frequency,
+    Waveform,
+    LfoRate,
+    LfoDepth,
+    Position,
+    Taper,
+    Curve,
+    MinimumVolume,
+    Speed,
+    MicAngle,
+    BaseFrequency,
+    Harmonics,
+    Tracking,
+    HissReduction,
+    TempoSync,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ParameterValue {
     Numeric(f32),
     String(String),
 }
 
+/// Average magnitude spectrum of the noise floor, learned while playing
+#[derive(Clone)]
+pub struct NoiseProfile {
+    pub sample_rate: u32,
+    pub magnitudes: Vec<f32>,
+}
+
 impl From<f32> for ParameterValue {
     fn from(value: f32) -> Self {
         ParameterValue::Numeric(value)
@@ -1,18 +1,22 @@
 use crate::processors::frontline::models::{Parameter, ParameterValue};
 use crate::processors::frontline::ocd::models::FilterMode;
 use crate::processors::frontline::ocd::tone_stack::ToneStack;
-use crate::processors::frontline::ocd::waveshaper::Waveshaper;
+use crate::processors::frontline::ocd::waveshaper::OcdClip;
 use crate::processors::frontline::FrontlineProcessor;
 use crate::processors::internal::filter::models::FirstOrderFilterType;
 use crate::processors::internal::filter::Filter;
 use crate::processors::internal::gain::Gain;
 use crate::processors::internal::resampler::Resampler;
-use crate::processors::Processor;
+use crate::processors::internal::waveshaper::models::Antialiasing;
+use crate::processors::internal::waveshaper::Waveshaper;
+use crate::processors::{ProcessingContext, Processor};
 
 mod models;
 mod tone_stack;
 mod waveshaper;
 
+static OVERSAMPLING_FACTOR: u32 = 2;
+
 /* Copied from https://github.com/JanosGit/Schrammel_OJD */
 pub struct Ocd {
     // Parameters
@@ -25,7 +29,7 @@ pub struct Ocd {
     pre_drive_notch_filter: Filter,
     gain: Gain,
     oversampler: Resampler,
-    waveshaper: Waveshaper,
+    waveshaper: Waveshaper<OcdClip>,
     downsampler: Resampler,
     post_drive_boost_1: Filter,
     post_drive_boost_2: Filter,
@@ -81,13 +85,17 @@ impl Processor for Ocd {
         let mut volume = Gain::new(sample_rate, block_size);
         let mut high_pass_filter = Filter::new(sample_rate, block_size);
         let mut low_pass_filter = Filter::new(sample_rate, block_size);
-        let waveshaper = Waveshaper::new(sample_rate, block_size);
+        let oversampled_rate = sample_rate * OVERSAMPLING_FACTOR;
+        let oversampled_block_size = block_size * OVERSAMPLING_FACTOR as usize;
+        let mut waveshaper = Waveshaper::new(&oversampled_rate, &oversampled_block_size);
+        waveshaper.antialiasing = Antialiasing::FirstOrder;
         let mut tone_stack = ToneStack::new(sample_rate, block_size);
 
+        // The waveshaper is anti-aliased, so a small oversampling factor is enough
         let mut oversampler = Resampler::new(sample_rate, block_size);
-        oversampler.set_target_sample_rate(sample_rate * 16);
-        let downsampler = Resampler::new(&(sample_rate * 16), &(block_size * 16));
-        oversampler.set_target_sample_rate(*sample_rate);
+        oversampler.set_target_sample_rate(oversampled_rate);
+        let mut downsampler = Resampler::new(&oversampled_rate, &oversampled_block_size);
+        downsampler.set_target_sample_rate(*sample_rate);
 
         gain.set_linear_gain(11.0);
         volume.set_db_range(-60.0, -20.0);
@@ -124,22 +132,21 @@ impl Processor for Ocd {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
         // TODO: filters clearly don't work well, as the resulting sound is very highs-only
-        // Plus, the resampler is too much expensive for the CPU.. Check if you can tweak that, otherwise I guess it's game over
-        self.high_pass_filter.process(data);
-        self.pre_drive_boost_filter.process(data);
-        self.pre_drive_notch_filter.process(data);
-        self.gain.process(data);
-        self.oversampler.process(data);
-        self.waveshaper.process(data);
-        self.downsampler.process(data);
-        self.post_drive_boost_1.process(data);
-        self.post_drive_boost_2.process(data);
-        self.post_drive_boost_3.process(data);
-        self.low_pass_filter.process(data);
-        self.tone_stack.process(data);
-        self.volume.process(data);
+        self.high_pass_filter.process(data, context);
+        self.pre_drive_boost_filter.process(data, context);
+        self.pre_drive_notch_filter.process(data, context);
+        self.gain.process(data, context);
+        self.oversampler.process(data, context);
+        self.waveshaper.process(data, context);
+        self.downsampler.process(data, context);
+        self.post_drive_boost_1.process(data, context);
+        self.post_drive_boost_2.process(data, context);
+        self.post_drive_boost_3.process(data, context);
+        self.low_pass_filter.process(data, context);
+        self.tone_stack.process(data, context);
+        self.volume.process(data, context);
     }
 }
 
@@ -2,7 +2,7 @@ use crate::processors::frontline::ocd::models::FilterMode;
 use crate::processors::internal::filter::models::FirstOrderFilterType;
 use crate::processors::internal::filter::Filter;
 use crate::processors::internal::gain::Gain;
-use crate::processors::Processor;
+use crate::processors::{ProcessingContext, Processor};
 
 pub struct ToneStack {
     filter_mode: FilterMode,
@@ -65,13 +65,13 @@ impl Processor for ToneStack {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
         // This truncates or extends the buffer without reallocating memory
         self.buffer.resize(data.len(), 0.0);
         self.buffer.copy_from_slice(data);
-        self.high_pass_filter.process(&mut self.buffer);
-        self.high_pass_gain.process(&mut self.buffer);
-        self.low_pass_filter.process(data);
+        self.high_pass_filter.process(&mut self.buffer, context);
+        self.high_pass_gain.process(&mut self.buffer, context);
+        self.low_pass_filter.process(data, context);
         for (sample, buffer_sample) in data.iter_mut().zip(&self.buffer) {
             *sample += *buffer_sample;
         }
@@ -1,31 +1,97 @@
-use crate::processors::Processor;
+use crate::processors::internal::waveshaper::ShapingFunction;
 
-pub struct Waveshaper {}
+const LOW_CLIP: f64 = -1.7;
+const LOW_THRESHOLD: f64 = -0.3;
+const HIGH_THRESHOLD: f64 = 0.9;
+const HIGH_CLIP: f64 = 1.1;
 
-impl Processor for Waveshaper {
-    fn new(_: &u32, _: &usize) -> Self
-    where
-        Self: Sized,
-    {
-        Self {}
+static LOW_KNEE: Knee = Knee {
+    threshold: LOW_THRESHOLD,
+    curvature: 1.0 / (4.0 * (1.0 + LOW_THRESHOLD)),
+};
+static HIGH_KNEE: Knee = Knee {
+    threshold: HIGH_THRESHOLD,
+    curvature: -1.0 / (4.0 * (1.0 - HIGH_THRESHOLD)),
+};
+
+/// Parabolic knee joining the linear region at `threshold` with the clipping region. The
+/// antiderivatives are anchored so that they are continuous with `x^2 / 2` and `x^3 / 6`.
+struct Knee {
+    threshold: f64,
+    curvature: f64,
+}
+
+impl Knee {
+    #[inline(always)]
+    fn evaluate(&self, x: f64) -> f64 {
+        let distance = x - self.threshold;
+        self.threshold + distance + self.curvature * distance.powi(2)
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
-        for sample in data {
-            *sample = match *sample {
-                ..=-1.7 => -1.0,
-                -1.7..=-0.3 => {
-                    *sample += 0.3;
-                    *sample + ((*sample).powi(2)) / (4.0 * (1.0 - 0.3)) - 0.3
-                }
-                0.9..=1.1 => {
-                    *sample -= 0.9;
-                    *sample - ((*sample).powi(2)) / (4.0 * (1.0 - 0.9)) + 0.9
-                }
-                1.1.. => 1.0,
-                _ => *sample,
+    fn antiderivative(&self, x: f64) -> f64 {
+        let distance = x - self.threshold;
+        self.threshold.powi(2) / 2.0
+            + self.threshold * distance
+            + distance.powi(2) / 2.0
+            + self.curvature * distance.powi(3) / 3.0
+    }
+
+    #[inline(always)]
+    fn second_antiderivative(&self, x: f64) -> f64 {
+        let distance = x - self.threshold;
+        self.threshold.powi(3) / 6.0
+            + self.threshold.powi(2) / 2.0 * distance
+            + self.threshold * distance.powi(2) / 2.0
+            + distance.powi(3) / 6.0
+            + self.curvature * distance.powi(4) / 12.0
+    }
+}
+
+#[derive(Default)]
+pub struct OcdClip {}
+
+impl ShapingFunction for OcdClip {
+    #[inline(always)]
+    fn evaluate(&self, x: f64) -> f64 {
+        match x {
+            ..=LOW_CLIP => -1.0,
+            LOW_CLIP..=LOW_THRESHOLD => LOW_KNEE.evaluate(x),
+            HIGH_THRESHOLD..=HIGH_CLIP => HIGH_KNEE.evaluate(x),
+            HIGH_CLIP.. => 1.0,
+            _ => x,
+        }
+    }
+
+    #[inline(always)]
+    fn antiderivative(&self, x: f64) -> f64 {
+        match x {
+            ..=LOW_CLIP => LOW_KNEE.antiderivative(LOW_CLIP) - (x - LOW_CLIP),
+            LOW_CLIP..=LOW_THRESHOLD => LOW_KNEE.antiderivative(x),
+            HIGH_THRESHOLD..=HIGH_CLIP => HIGH_KNEE.antiderivative(x),
+            HIGH_CLIP.. => HIGH_KNEE.antiderivative(HIGH_CLIP) + (x - HIGH_CLIP),
+            _ => x.powi(2) / 2.0,
+        }
+    }
+
+    #[inline(always)]
+    fn second_antiderivative(&self, x: f64) -> f64 {
+        match x {
+            ..=LOW_CLIP => {
+                let distance = x - LOW_CLIP;
+                LOW_KNEE.second_antiderivative(LOW_CLIP)
+                    + LOW_KNEE.antiderivative(LOW_CLIP) * distance
+                    - distance.powi(2) / 2.0
+            }
+            LOW_CLIP..=LOW_THRESHOLD => LOW_KNEE.second_antiderivative(x),
+            HIGH_THRESHOLD..=HIGH_CLIP => HIGH_KNEE.second_antiderivative(x),
+            HIGH_CLIP.. => {
+                let distance = x - HIGH_CLIP;
+                HIGH_KNEE.second_antiderivative(HIGH_CLIP)
+                    + HIGH_KNEE.antiderivative(HIGH_CLIP) * distance
+                    + distance.powi(2) / 2.0
             }
+            _ => x.powi(3) / 6.0,
         }
     }
 }
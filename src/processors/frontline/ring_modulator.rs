@@ -0,0 +1,119 @@
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::FrontlineProcessor;
+use crate::processors::internal::oscillator::models::Waveform;
+use crate::processors::internal::oscillator::Oscillator;
+use crate::processors::{ProcessingContext, Processor};
+
+static MIN_CARRIER_FREQUENCY: f32 = 20.0;
+static MAX_CARRIER_FREQUENCY: f32 = 4_000.0;
+static MIN_LFO_FREQUENCY: f32 = 0.05;
+static MAX_LFO_FREQUENCY: f32 = 10.0;
+static MAX_LFO_DEPTH_OCTAVES: f32 = 2.0;
+
+pub struct RingModulator {
+    // Parameters
+    frequency: f32,
+    lfo_rate: f32,
+    lfo_depth: f32,
+    mix: f32,
+    tempo_sync: bool,
+
+    // Chain
+    carrier: Oscillator,
+    lfo: Oscillator,
+}
+
+impl RingModulator {
+    fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency.clamp(0.0, 1.0);
+        self.carrier.set_frequency(
+            MIN_CARRIER_FREQUENCY
+                * (MAX_CARRIER_FREQUENCY / MIN_CARRIER_FREQUENCY).powf(self.frequency),
+        );
+    }
+
+    fn set_lfo_rate(&mut self, lfo_rate: f32) {
+        self.lfo_rate = lfo_rate.clamp(0.0, 1.0);
+        self.lfo.set_frequency(
+            MIN_LFO_FREQUENCY * (MAX_LFO_FREQUENCY / MIN_LFO_FREQUENCY).powf(self.lfo_rate),
+        );
+    }
+
+    fn set_tempo_sync(&mut self, tempo_sync: bool) {
+        self.tempo_sync = tempo_sync;
+        if !tempo_sync {
+            self.set_lfo_rate(self.lfo_rate);
+        }
+    }
+}
+
+impl Processor for RingModulator {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self {
+        let mut processor = Self {
+            frequency: 0.0,
+            lfo_rate: 0.0,
+            lfo_depth: 0.0,
+            mix: 0.5,
+            tempo_sync: false,
+            carrier: Oscillator::new(sample_rate, block_size),
+            lfo: Oscillator::new(sample_rate, block_size),
+        };
+        processor.set_frequency(0.5);
+        processor.set_lfo_rate(0.3);
+        processor
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
+        if self.tempo_sync {
+            // One LFO cycle per beat
+            self.lfo.set_frequency(context.tempo / 60.0);
+        }
+        let carrier_frequency = self.carrier.get_frequency();
+        for sample in data.iter_mut() {
+            let modulation = self.lfo.next_sample() * self.lfo_depth * MAX_LFO_DEPTH_OCTAVES;
+            let carrier = self
+                .carrier
+                .next_sample_with_frequency(carrier_frequency * modulation.exp2());
+            *sample = *sample * (1.0 - self.mix) + *sample * carrier * self.mix;
+        }
+    }
+}
+
+impl FrontlineProcessor for RingModulator {
+    fn get_parameter(&self, parameter: Parameter) -> Option<ParameterValue> {
+        match parameter {
+            Parameter::Frequency => Some(self.frequency.into()),
+            Parameter::Waveform => Some(f32::from(self.carrier.waveform).into()),
+            Parameter::LfoRate => Some(self.lfo_rate.into()),
+            Parameter::LfoDepth => Some(self.lfo_depth.into()),
+            Parameter::Mix => Some(self.mix.into()),
+            Parameter::TempoSync => Some(if self.tempo_sync { 1.0 } else { 0.0 }.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, parameter: Parameter, value: ParameterValue) {
+        match (parameter, value) {
+            (Parameter::Frequency, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_frequency(wrapped_value)
+            }
+            (Parameter::Waveform, ParameterValue::Numeric(wrapped_value)) => {
+                self.carrier.waveform = Waveform::from(wrapped_value)
+            }
+            (Parameter::LfoRate, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_lfo_rate(wrapped_value)
+            }
+            (Parameter::LfoDepth, ParameterValue::Numeric(wrapped_value)) => {
+                self.lfo_depth = wrapped_value.clamp(0.0, 1.0)
+            }
+            (Parameter::Mix, ParameterValue::Numeric(wrapped_value)) => {
+                self.mix = wrapped_value.clamp(0.0, 1.0)
+            }
+            (Parameter::TempoSync, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_tempo_sync(wrapped_value > 0.5)
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::rotary::models::{RotorCharacteristics, RotorSpeed};
+use crate::processors::frontline::rotary::rotor::Rotor;
+use crate::processors::frontline::FrontlineProcessor;
+use crate::processors::internal::filter::models::SecondOrderFilterType;
+use crate::processors::internal::filter::Filter;
+use crate::processors::internal::gain::Gain;
+use crate::processors::{ProcessingContext, Processor};
+
+mod models;
+mod rotor;
+
+static CROSSOVER_FREQUENCY: f32 = 800.0;
+static CROSSOVER_Q: f32 = 0.707;
+
+static HORN_CHARACTERISTICS: RotorCharacteristics = RotorCharacteristics {
+    slow_frequency: 0.8,
+    fast_frequency: 6.7,
+    acceleration_time: 0.7,
+    deceleration_time: 1.5,
+    base_delay_ms: 2.0,
+    doppler_depth_ms: 0.35,
+    amplitude_modulation_depth: 0.5,
+};
+
+static DRUM_CHARACTERISTICS: RotorCharacteristics = RotorCharacteristics {
+    slow_frequency: 0.7,
+    fast_frequency: 5.9,
+    acceleration_time: 4.0,
+    deceleration_time: 5.0,
+    base_delay_ms: 3.0,
+    doppler_depth_ms: 0.2,
+    amplitude_modulation_depth: 0.3,
+};
+
+/* Leslie-style cabinet: the horn plays the highs and the drum the lows, each spinning on its own */
+pub struct Rotary {
+    // Parameters
+    speed: RotorSpeed,
+    // Mono in and out: the angle of the summed microphones, not a stereo width
+    mic_angle: f32,
+
+    // Chain
+    drive: Gain,
+    buffer: Vec<f32>,
+    horn_filter: Filter,
+    drum_filter: Filter,
+    horn: Rotor,
+    drum: Rotor,
+}
+
+impl Rotary {
+    fn set_speed(&mut self, speed: RotorSpeed) {
+        self.speed = speed;
+        self.horn.speed = speed;
+        self.drum.speed = speed;
+    }
+
+    fn set_mic_angle(&mut self, mic_angle: f32) {
+        self.mic_angle = mic_angle.clamp(0.0, 1.0);
+        self.horn.mic_angle = self.mic_angle;
+        self.drum.mic_angle = self.mic_angle;
+    }
+}
+
+impl Processor for Rotary {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self {
+        let mut drive = Gain::new(sample_rate, block_size);
+        drive.set_db_range(0.0, 24.0);
+        drive.decimal = 0.2;
+
+        let mut horn_filter = Filter::new(sample_rate, block_size);
+        horn_filter.make_second_order(
+            SecondOrderFilterType::HighPass,
+            CROSSOVER_FREQUENCY,
+            CROSSOVER_Q,
+        );
+        let mut drum_filter = Filter::new(sample_rate, block_size);
+        drum_filter.make_second_order(
+            SecondOrderFilterType::LowPass,
+            CROSSOVER_FREQUENCY,
+            CROSSOVER_Q,
+        );
+
+        let mut horn = Rotor::new(sample_rate, block_size);
+        horn.set_characteristics(&HORN_CHARACTERISTICS);
+        let mut drum = Rotor::new(sample_rate, block_size);
+        drum.set_characteristics(&DRUM_CHARACTERISTICS);
+
+        let mut processor = Self {
+            speed: RotorSpeed::Slow,
+            mic_angle: 0.5,
+            drive,
+            buffer: vec![0.0; *block_size],
+            horn_filter,
+            drum_filter,
+            horn,
+            drum,
+        };
+        processor.set_speed(RotorSpeed::Slow);
+        processor.set_mic_angle(0.5);
+        processor
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
+        self.drive.process(data, context);
+        for sample in data.iter_mut() {
+            *sample = (*sample).tanh();
+        }
+
+        // This truncates or extends the buffer without reallocating memory
+        self.buffer.resize(data.len(), 0.0);
+        self.buffer.copy_from_slice(data);
+        self.drum_filter.process(&mut self.buffer, context);
+        self.drum.process(&mut self.buffer, context);
+        self.horn_filter.process(data, context);
+        self.horn.process(data, context);
+        for (sample, buffer_sample) in data.iter_mut().zip(&self.buffer) {
+            *sample += *buffer_sample;
+        }
+    }
+}
+
+impl FrontlineProcessor for Rotary {
+    fn get_parameter(&self, parameter: Parameter) -> Option<ParameterValue> {
+        match parameter {
+            Parameter::Speed => Some(match self.speed {
+                RotorSpeed::Slow => 0.0.into(),
+                RotorSpeed::Fast => 1.0.into(),
+            }),
+            Parameter::Drive => Some(self.drive.decimal.into()),
+            Parameter::MicAngle => Some(self.mic_angle.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, parameter: Parameter, value: ParameterValue) {
+        match (parameter, value) {
+            (Parameter::Speed, ParameterValue::Numeric(0.0..=0.5)) => {
+                self.set_speed(RotorSpeed::Slow)
+            }
+            (Parameter::Speed, ParameterValue::Numeric(0.5..=1.0)) => {
+                self.set_speed(RotorSpeed::Fast)
+            }
+            (Parameter::Drive, ParameterValue::Numeric(wrapped_value)) => {
+                self.drive.decimal = wrapped_value
+            }
+            (Parameter::MicAngle, ParameterValue::Numeric(wrapped_value)) => {
+                self.set_mic_angle(wrapped_value)
+            }
+            _ => {}
+        }
+    }
+}
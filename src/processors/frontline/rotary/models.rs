@@ -0,0 +1,15 @@
+#[derive(Clone, Copy)]
+pub enum RotorSpeed {
+    Slow,
+    Fast,
+}
+
+pub struct RotorCharacteristics {
+    pub slow_frequency: f32,
+    pub fast_frequency: f32,
+    pub acceleration_time: f32,
+    pub deceleration_time: f32,
+    pub base_delay_ms: f32,
+    pub doppler_depth_ms: f32,
+    pub amplitude_modulation_depth: f32,
+}
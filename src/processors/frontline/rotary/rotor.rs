@@ -0,0 +1,105 @@
+use crate::processors::frontline::rotary::models::{RotorCharacteristics, RotorSpeed};
+use crate::processors::internal::delay_line::DelayLine;
+use crate::processors::{ProcessingContext, Processor};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+pub struct Rotor {
+    pub speed: RotorSpeed,
+    pub mic_angle: f32,
+    slow_frequency: f32,
+    fast_frequency: f32,
+    acceleration_coefficient: f32,
+    deceleration_coefficient: f32,
+    base_delay_in_samples: f32,
+    doppler_depth_in_samples: f32,
+    amplitude_modulation_depth: f32,
+    delay_line: DelayLine,
+    frequency: f32,
+    phase: f32,
+    sample_rate: f32,
+}
+
+impl Rotor {
+    pub fn set_characteristics(&mut self, characteristics: &RotorCharacteristics) {
+        let samples_per_ms = self.sample_rate / 1_000.0;
+
+        self.slow_frequency = characteristics.slow_frequency;
+        self.fast_frequency = characteristics.fast_frequency;
+        self.acceleration_coefficient =
+            (-1.0 / (characteristics.acceleration_time * self.sample_rate)).exp();
+        self.deceleration_coefficient =
+            (-1.0 / (characteristics.deceleration_time * self.sample_rate)).exp();
+        self.base_delay_in_samples = characteristics.base_delay_ms * samples_per_ms;
+        self.doppler_depth_in_samples = characteristics.doppler_depth_ms * samples_per_ms;
+        self.amplitude_modulation_depth = characteristics.amplitude_modulation_depth;
+        self.frequency = self.slow_frequency;
+
+        self.delay_line.set_max_delay_in_samples(
+            (self.base_delay_in_samples + self.doppler_depth_in_samples).ceil() as usize + 1,
+        );
+    }
+
+    #[inline(always)]
+    fn read_microphone(&self, rotor_angle: f32, microphone_angle: f32) -> f32 {
+        let relative_angle = rotor_angle - microphone_angle;
+        let delay =
+            self.base_delay_in_samples + self.doppler_depth_in_samples * relative_angle.sin();
+        let amplitude = 1.0 - self.amplitude_modulation_depth * 0.5 * (1.0 - relative_angle.cos());
+        self.delay_line.read(delay) * amplitude
+    }
+}
+
+impl Processor for Rotor {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            speed: RotorSpeed::Slow,
+            mic_angle: 0.5,
+            slow_frequency: 0.0,
+            fast_frequency: 0.0,
+            acceleration_coefficient: 0.0,
+            deceleration_coefficient: 0.0,
+            base_delay_in_samples: 0.0,
+            doppler_depth_in_samples: 0.0,
+            amplitude_modulation_depth: 0.0,
+            delay_line: DelayLine::new(sample_rate, block_size),
+            frequency: 0.0,
+            phase: 0.0,
+            sample_rate: *sample_rate as f32,
+        }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        let target_frequency = match self.speed {
+            RotorSpeed::Slow => self.slow_frequency,
+            RotorSpeed::Fast => self.fast_frequency,
+        };
+        let ramp_coefficient = if target_frequency > self.frequency {
+            self.acceleration_coefficient
+        } else {
+            self.deceleration_coefficient
+        };
+        // Two microphones placed symmetrically around the cabinet, up to 90 degrees off axis each.
+        // They are summed to mono, so their angle blends how much Doppler and tremolo come through.
+        let microphone_angle = self.mic_angle * FRAC_PI_2;
+
+        for sample in data.iter_mut() {
+            self.delay_line.write(*sample);
+
+            self.frequency =
+                target_frequency + (self.frequency - target_frequency) * ramp_coefficient;
+            self.phase += self.frequency / self.sample_rate;
+            if self.phase >= 1.0 {
+                self.phase -= 1.0;
+            }
+
+            let rotor_angle = TAU * self.phase;
+            *sample = 0.5
+                * (self.read_microphone(rotor_angle, -microphone_angle)
+                    + self.read_microphone(rotor_angle, microphone_angle));
+        }
+    }
+}
@@ -1,16 +1,21 @@
 use crate::processors::frontline::models::{Parameter, ParameterValue};
 use crate::processors::frontline::FrontlineProcessor;
 use crate::processors::internal::gain::Gain;
-use crate::processors::Processor;
+use crate::processors::internal::waveshaper::functions::Tanh;
+use crate::processors::internal::waveshaper::models::Antialiasing;
+use crate::processors::internal::waveshaper::Waveshaper;
+use crate::processors::{ProcessingContext, Processor};
 
 pub struct SimpleDistortion {
     drive: Gain,
+    waveshaper: Waveshaper<Tanh>,
     volume: Gain,
 }
 
 impl Processor for SimpleDistortion {
     fn new(sample_rate: &u32, block_size: &usize) -> Self {
         let mut drive = Gain::new(sample_rate, block_size);
+        let mut waveshaper = Waveshaper::new(sample_rate, block_size);
         let mut volume = Gain::new(sample_rate, block_size);
 
         drive.set_db_range(2.0, 34.0);
@@ -19,16 +24,20 @@ impl Processor for SimpleDistortion {
         volume.set_db_range(-15.0, 3.0);
         volume.decimal = 0.5;
 
-        Self { drive, volume }
+        waveshaper.antialiasing = Antialiasing::SecondOrder;
+
+        Self {
+            drive,
+            waveshaper,
+            volume,
+        }
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
-        self.drive.process(data);
-        for sample in data.iter_mut() {
-            *sample = (*sample).tanh();
-        }
-        self.volume.process(data);
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext) {
+        self.drive.process(data, context);
+        self.waveshaper.process(data, context);
+        self.volume.process(data, context);
     }
 }
 
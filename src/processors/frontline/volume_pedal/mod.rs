@@ -0,0 +1,91 @@
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use crate::processors::frontline::volume_pedal::models::Taper;
+use crate::processors::frontline::FrontlineProcessor;
+use crate::processors::internal::smoother::Smoother;
+use crate::processors::{ProcessingContext, Processor};
+
+mod models;
+
+static LOGARITHMIC_TAPER_BASE: f32 = 100.0;
+static MAX_CUSTOM_CURVE_EXPONENT_LOG2: f32 = 3.0;
+
+pub struct VolumePedal {
+    // Parameters
+    taper: Taper,
+    curve: f32,
+    minimum_volume: f32,
+
+    // Chain
+    position: Smoother,
+}
+
+impl VolumePedal {
+    #[inline(always)]
+    fn get_linear_gain(&self, position: f32) -> f32 {
+        let tapered_position = match self.taper {
+            Taper::Linear => position,
+            Taper::Logarithmic => {
+                (LOGARITHMIC_TAPER_BASE.powf(position) - 1.0) / (LOGARITHMIC_TAPER_BASE - 1.0)
+            }
+            Taper::Custom => {
+                // The curve maps to an exponent between 1/8 (fast rise) and 8 (slow rise)
+                let exponent = ((self.curve * 2.0 - 1.0) * MAX_CUSTOM_CURVE_EXPONENT_LOG2).exp2();
+                position.powf(exponent)
+            }
+        };
+        self.minimum_volume + (1.0 - self.minimum_volume) * tapered_position
+    }
+}
+
+impl Processor for VolumePedal {
+    fn new(sample_rate: &u32, block_size: &usize) -> Self {
+        let mut position = Smoother::new(sample_rate, block_size);
+        position.set_time_ms(30.0);
+        position.reset(1.0);
+
+        Self {
+            taper: Taper::Logarithmic,
+            curve: 0.5,
+            minimum_volume: 0.0,
+            position,
+        }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            let position = self.position.next_sample();
+            *sample *= self.get_linear_gain(position);
+        }
+    }
+}
+
+impl FrontlineProcessor for VolumePedal {
+    fn get_parameter(&self, parameter: Parameter) -> Option<ParameterValue> {
+        match parameter {
+            Parameter::Position => Some(self.position.target.into()),
+            Parameter::Taper => Some(f32::from(self.taper).into()),
+            Parameter::Curve => Some(self.curve.into()),
+            Parameter::MinimumVolume => Some(self.minimum_volume.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, parameter: Parameter, value: ParameterValue) {
+        match (parameter, value) {
+            (Parameter::Position, ParameterValue::Numeric(wrapped_value)) => {
+                self.position.target = wrapped_value.clamp(0.0, 1.0)
+            }
+            (Parameter::Taper, ParameterValue::Numeric(wrapped_value)) => {
+                self.taper = Taper::from(wrapped_value)
+            }
+            (Parameter::Curve, ParameterValue::Numeric(wrapped_value)) => {
+                self.curve = wrapped_value.clamp(0.0, 1.0)
+            }
+            (Parameter::MinimumVolume, ParameterValue::Numeric(wrapped_value)) => {
+                self.minimum_volume = wrapped_value.clamp(0.0, 1.0)
+            }
+            _ => {}
+        }
+    }
+}
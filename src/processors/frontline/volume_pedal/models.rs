@@ -0,0 +1,26 @@
+#[derive(Clone, Copy)]
+pub enum Taper {
+    Linear,
+    Logarithmic,
+    Custom,
+}
+
+impl From<f32> for Taper {
+    fn from(value: f32) -> Self {
+        match value {
+            ..=0.25 => Taper::Linear,
+            0.25..=0.75 => Taper::Logarithmic,
+            _ => Taper::Custom,
+        }
+    }
+}
+
+impl From<Taper> for f32 {
+    fn from(value: Taper) -> Self {
+        match value {
+            Taper::Linear => 0.0,
+            Taper::Logarithmic => 0.5,
+            Taper::Custom => 1.0,
+        }
+    }
+}
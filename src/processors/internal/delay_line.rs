@@ -0,0 +1,57 @@
+use crate::processors::{ProcessingContext, Processor};
+
+pub struct DelayLine {
+    pub delay_in_samples: f32,
+    buffer: Vec<f32>,
+    write_index: usize,
+}
+
+impl DelayLine {
+    pub fn set_max_delay_in_samples(&mut self, max_delay_in_samples: usize) {
+        self.buffer = vec![0.0; max_delay_in_samples + 2];
+        self.write_index = 0;
+    }
+
+    #[inline(always)]
+    pub fn write(&mut self, sample: f32) {
+        self.buffer[self.write_index] = sample;
+        self.write_index = (self.write_index + 1) % self.buffer.len();
+    }
+
+    /// Reads the sample written `delay_in_samples` samples ago, with linear interpolation
+    /// between the two closest samples.
+    #[inline(always)]
+    pub fn read(&self, delay_in_samples: f32) -> f32 {
+        let length = self.buffer.len();
+        let delay_in_samples = delay_in_samples.clamp(0.0, (length - 2) as f32);
+        let integer_delay = delay_in_samples.floor() as usize;
+        let fraction = delay_in_samples - integer_delay as f32;
+
+        let newer_index = (self.write_index + length - 1 - integer_delay) % length;
+        let older_index = (newer_index + length - 1) % length;
+        self.buffer[newer_index] * (1.0 - fraction) + self.buffer[older_index] * fraction
+    }
+}
+
+impl Processor for DelayLine {
+    fn new(sample_rate: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        let mut delay_line = Self {
+            delay_in_samples: 0.0,
+            buffer: vec![],
+            write_index: 0,
+        };
+        delay_line.set_max_delay_in_samples(*sample_rate as usize);
+        delay_line
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            self.write(*sample);
+            *sample = self.read(self.delay_in_samples);
+        }
+    }
+}
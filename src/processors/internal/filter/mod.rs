@@ -1,5 +1,5 @@
-use crate::processors::internal::filter::models::FirstOrderFilterType;
-use crate::processors::Processor;
+use crate::processors::internal::filter::models::{FirstOrderFilterType, SecondOrderFilterType};
+use crate::processors::{ProcessingContext, Processor};
 use std::f32::consts::PI;
 
 pub mod models;
@@ -40,6 +40,42 @@ impl Filter {
         self.reset_state();
     }
 
+    pub fn make_second_order(
+        &mut self,
+        filter_type: SecondOrderFilterType,
+        frequency: f32,
+        q: f32,
+    ) {
+        let omega = 2.0 * PI * frequency / self.sample_rate;
+        let alpha = omega.sin() / (2.0 * q);
+        let cos_omega = omega.cos();
+
+        let numerators = [1.0 + alpha, -2.0 * cos_omega, 1.0 - alpha];
+        let denominators = match filter_type {
+            SecondOrderFilterType::LowPass => [
+                (1.0 - cos_omega) / 2.0,
+                1.0 - cos_omega,
+                (1.0 - cos_omega) / 2.0,
+            ],
+            SecondOrderFilterType::HighPass => [
+                (1.0 + cos_omega) / 2.0,
+                -(1.0 + cos_omega),
+                (1.0 + cos_omega) / 2.0,
+            ],
+            SecondOrderFilterType::Notch => [1.0, -2.0 * cos_omega, 1.0],
+        };
+
+        if self.get_order() == 2 {
+            // Same order: keep the state, so that the filter can be retuned while running
+            self.numerators.copy_from_slice(&numerators);
+            self.denominators.copy_from_slice(&denominators);
+        } else {
+            self.numerators = numerators.to_vec();
+            self.denominators = denominators.to_vec();
+            self.reset_state();
+        }
+    }
+
     pub fn make_peak(&mut self, frequency: f32, q: f32, gain_db: f32) {
         let linear_gain = 10.0_f32.powf(gain_db / 20.0);
         let omega = 2.0 * PI * frequency / self.sample_rate;
@@ -76,7 +112,7 @@ impl Processor for Filter {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
         for sample in data {
             for history_index in (1..self.input_history.len()).rev() {
                 self.input_history[history_index] = self.input_history[history_index - 1];
@@ -2,3 +2,9 @@ pub enum FirstOrderFilterType {
     LowPass,
     HighPass,
 }
+
+pub enum SecondOrderFilterType {
+    LowPass,
+    HighPass,
+    Notch,
+}
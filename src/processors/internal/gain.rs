@@ -1,4 +1,4 @@
-use crate::processors::Processor;
+use crate::processors::{ProcessingContext, Processor};
 
 pub struct Gain {
     pub decimal: f32,
@@ -36,7 +36,7 @@ impl Processor for Gain {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
         let gain = self.get_linear_gain();
         for sample in data.iter_mut() {
             *sample *= gain
@@ -1,3 +1,8 @@
+pub mod delay_line;
 pub mod filter;
 pub mod gain;
+pub mod noise;
+pub mod oscillator;
 pub mod resampler;
+pub mod smoother;
+pub mod waveshaper;
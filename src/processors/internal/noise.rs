@@ -0,0 +1,38 @@
+use crate::processors::{ProcessingContext, Processor};
+
+/// Cheap xorshift white noise generator, safe to use in the audio thread.
+pub struct Noise {
+    state: u32,
+}
+
+impl Noise {
+    #[inline(always)]
+    pub fn next_sample(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        (self.state as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    /// Triangular-PDF noise in the range (-1, 1), as used for dithering.
+    #[inline(always)]
+    pub fn next_triangular_sample(&mut self) -> f32 {
+        (self.next_sample() + self.next_sample()) * 0.5
+    }
+}
+
+impl Processor for Noise {
+    fn new(_: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self { state: 0x9E37_79B9 }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::processors::internal::oscillator::models::Waveform;
+use crate::processors::{ProcessingContext, Processor};
+use std::f32::consts::TAU;
+
+pub mod models;
+
+pub struct Oscillator {
+    pub waveform: Waveform,
+    frequency: f32,
+    phase: f32,
+    sample_rate: f32,
+}
+
+impl Oscillator {
+    pub fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+    }
+
+    pub fn get_frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    #[inline(always)]
+    pub fn next_sample(&mut self) -> f32 {
+        self.next_sample_with_frequency(self.frequency)
+    }
+
+    #[inline(always)]
+    pub fn next_sample_with_frequency(&mut self, frequency: f32) -> f32 {
+        let value = match self.waveform {
+            Waveform::Sine => (TAU * self.phase).sin(),
+            Waveform::Triangle => 1.0 - 4.0 * (self.phase - 0.5).abs(),
+            Waveform::Square => {
+                if self.phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Sawtooth => 2.0 * self.phase - 1.0,
+        };
+
+        self.phase += frequency / self.sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= self.phase.floor();
+        }
+
+        value
+    }
+}
+
+impl Processor for Oscillator {
+    fn new(sample_rate: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            waveform: Waveform::Sine,
+            frequency: 1.0,
+            phase: 0.0,
+            sample_rate: *sample_rate as f32,
+        }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+}
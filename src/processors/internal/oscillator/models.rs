@@ -0,0 +1,50 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Waveform {
+    Sine,
+    Triangle,
+    Square,
+    Sawtooth,
+}
+
+impl From<f32> for Waveform {
+    fn from(value: f32) -> Self {
+        // Halfway between the encoded values below
+        if value < 1.0 / 6.0 {
+            Waveform::Sine
+        } else if value < 0.5 {
+            Waveform::Triangle
+        } else if value < 5.0 / 6.0 {
+            Waveform::Square
+        } else {
+            Waveform::Sawtooth
+        }
+    }
+}
+
+impl From<Waveform> for f32 {
+    fn from(value: Waveform) -> Self {
+        match value {
+            Waveform::Sine => 0.0,
+            Waveform::Triangle => 1.0 / 3.0,
+            Waveform::Square => 2.0 / 3.0,
+            Waveform::Sawtooth => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_waveform_round_trips_through_its_value() {
+        for waveform in [
+            Waveform::Sine,
+            Waveform::Triangle,
+            Waveform::Square,
+            Waveform::Sawtooth,
+        ] {
+            assert_eq!(Waveform::from(f32::from(waveform)), waveform);
+        }
+    }
+}
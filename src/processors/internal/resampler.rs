@@ -1,4 +1,4 @@
-use crate::processors::Processor;
+use crate::processors::{ProcessingContext, Processor};
 use rubato::{FftFixedInOut, Resampler as RubatoResampler};
 
 pub struct Resampler {
@@ -49,7 +49,7 @@ impl Processor for Resampler {
     }
 
     #[inline(always)]
-    fn process(&mut self, data: &mut Vec<f32>) {
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
         self.input_buffer[0].resize(data.len(), 0.0);
         self.input_buffer[0].copy_from_slice(data);
         self.input_buffer[0].resize(self.resampler.input_frames_max(), 0.0);
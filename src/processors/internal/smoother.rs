@@ -0,0 +1,50 @@
+use crate::processors::{ProcessingContext, Processor};
+
+/// One-pole smoother used to avoid zipper noise when a parameter jumps.
+pub struct Smoother {
+    pub target: f32,
+    coefficient: f32,
+    sample_rate: f32,
+    value: f32,
+}
+
+impl Smoother {
+    pub fn set_time_ms(&mut self, time_ms: f32) {
+        let time_in_samples = (time_ms / 1_000.0 * self.sample_rate).max(1.0);
+        self.coefficient = (-1.0 / time_in_samples).exp();
+    }
+
+    pub fn reset(&mut self, value: f32) {
+        self.target = value;
+        self.value = value;
+    }
+
+    #[inline(always)]
+    pub fn next_sample(&mut self) -> f32 {
+        self.value = self.target + (self.value - self.target) * self.coefficient;
+        self.value
+    }
+}
+
+impl Processor for Smoother {
+    fn new(sample_rate: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        let mut smoother = Self {
+            target: 0.0,
+            coefficient: 0.0,
+            sample_rate: *sample_rate as f32,
+            value: 0.0,
+        };
+        smoother.set_time_ms(20.0);
+        smoother
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        for sample in data.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+}
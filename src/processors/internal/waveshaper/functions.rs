@@ -0,0 +1,118 @@
+use crate::processors::internal::waveshaper::ShapingFunction;
+
+const TANH_TABLE_RANGE: f64 = 5.0;
+const TANH_TABLE_SIZE: usize = 2048;
+
+/// Lookup-table shaper: the function is sampled on a uniform grid and linearly interpolated.
+/// The antiderivatives are integrated exactly from the interpolated curve, so any function can be
+/// used with ADAA. Outside the table the curve is held at its boundary values.
+pub struct TableShaper {
+    minimum: f64,
+    maximum: f64,
+    step: f64,
+    values: Vec<f64>,
+    slopes: Vec<f64>,
+    antiderivatives: Vec<f64>,
+    second_antiderivatives: Vec<f64>,
+}
+
+impl TableShaper {
+    pub fn from_function(function: impl Fn(f64) -> f64, range: f64, size: usize) -> Self {
+        let size = size.max(2);
+        let step = 2.0 * range / (size - 1) as f64;
+        let values: Vec<f64> = (0..size)
+            .map(|index| function(-range + index as f64 * step))
+            .collect();
+
+        let mut slopes = vec![0.0; size];
+        let mut antiderivatives = vec![0.0; size];
+        let mut second_antiderivatives = vec![0.0; size];
+        for index in 0..size - 1 {
+            let slope = (values[index + 1] - values[index]) / step;
+            slopes[index] = slope;
+            antiderivatives[index + 1] =
+                antiderivatives[index] + values[index] * step + slope * step.powi(2) / 2.0;
+            second_antiderivatives[index + 1] = second_antiderivatives[index]
+                + antiderivatives[index] * step
+                + values[index] * step.powi(2) / 2.0
+                + slope * step.powi(3) / 6.0;
+        }
+
+        Self {
+            minimum: -range,
+            maximum: range,
+            step,
+            values,
+            slopes,
+            antiderivatives,
+            second_antiderivatives,
+        }
+    }
+
+    /// Returns the segment index, the offset from the segment start and the segment slope
+    #[inline(always)]
+    fn locate(&self, x: f64) -> (usize, f64, f64) {
+        let last_index = self.values.len() - 1;
+        if x <= self.minimum {
+            (0, x - self.minimum, 0.0)
+        } else if x >= self.maximum {
+            (last_index, x - self.maximum, 0.0)
+        } else {
+            let index = (((x - self.minimum) / self.step) as usize).min(last_index - 1);
+            let offset = x - (self.minimum + index as f64 * self.step);
+            (index, offset, self.slopes[index])
+        }
+    }
+}
+
+impl ShapingFunction for TableShaper {
+    #[inline(always)]
+    fn evaluate(&self, x: f64) -> f64 {
+        let (index, offset, slope) = self.locate(x);
+        self.values[index] + slope * offset
+    }
+
+    #[inline(always)]
+    fn antiderivative(&self, x: f64) -> f64 {
+        let (index, offset, slope) = self.locate(x);
+        self.antiderivatives[index] + self.values[index] * offset + slope * offset.powi(2) / 2.0
+    }
+
+    #[inline(always)]
+    fn second_antiderivative(&self, x: f64) -> f64 {
+        let (index, offset, slope) = self.locate(x);
+        self.second_antiderivatives[index]
+            + self.antiderivatives[index] * offset
+            + self.values[index] * offset.powi(2) / 2.0
+            + slope * offset.powi(3) / 6.0
+    }
+}
+
+pub struct Tanh {
+    table: TableShaper,
+}
+
+impl Default for Tanh {
+    fn default() -> Self {
+        Self {
+            table: TableShaper::from_function(f64::tanh, TANH_TABLE_RANGE, TANH_TABLE_SIZE),
+        }
+    }
+}
+
+impl ShapingFunction for Tanh {
+    #[inline(always)]
+    fn evaluate(&self, x: f64) -> f64 {
+        self.table.evaluate(x)
+    }
+
+    #[inline(always)]
+    fn antiderivative(&self, x: f64) -> f64 {
+        self.table.antiderivative(x)
+    }
+
+    #[inline(always)]
+    fn second_antiderivative(&self, x: f64) -> f64 {
+        self.table.second_antiderivative(x)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::processors::internal::waveshaper::models::Antialiasing;
+use crate::processors::{ProcessingContext, Processor};
+
+pub mod functions;
+pub mod models;
+
+// Below this input difference the divided differences are ill-conditioned
+const ILL_CONDITIONING_THRESHOLD: f64 = 1e-5;
+
+/// A static nonlinearity, together with its first and second antiderivatives so that it can be
+/// evaluated with antiderivative anti-aliasing (ADAA).
+pub trait ShapingFunction {
+    fn evaluate(&self, x: f64) -> f64;
+    fn antiderivative(&self, x: f64) -> f64;
+    fn second_antiderivative(&self, x: f64) -> f64;
+}
+
+/* See Parker et al., "Reducing the aliasing of nonlinear waveshaping using continuous-time convolution" and Bilbao et al., "Antiderivative antialiasing for memoryless nonlinearities" */
+pub struct Waveshaper<Function: ShapingFunction> {
+    pub antialiasing: Antialiasing,
+    function: Function,
+    previous_input: f64,
+    second_previous_input: f64,
+    previous_divided_difference: f64,
+}
+
+impl<Function: ShapingFunction> Waveshaper<Function> {
+    #[inline(always)]
+    fn process_first_order(&mut self, input: f64) -> f64 {
+        let difference = input - self.previous_input;
+        let output = if difference.abs() < ILL_CONDITIONING_THRESHOLD {
+            self.function.evaluate(0.5 * (input + self.previous_input))
+        } else {
+            (self.function.antiderivative(input)
+                - self.function.antiderivative(self.previous_input))
+                / difference
+        };
+        self.previous_input = input;
+        output
+    }
+
+    #[inline(always)]
+    fn divided_difference(&self, input: f64, previous_input: f64) -> f64 {
+        let difference = input - previous_input;
+        if difference.abs() < ILL_CONDITIONING_THRESHOLD {
+            self.function.antiderivative(0.5 * (input + previous_input))
+        } else {
+            (self.function.second_antiderivative(input)
+                - self.function.second_antiderivative(previous_input))
+                / difference
+        }
+    }
+
+    #[inline(always)]
+    fn process_second_order(&mut self, input: f64) -> f64 {
+        let divided_difference = self.divided_difference(input, self.previous_input);
+        let difference = input - self.second_previous_input;
+
+        let output = if difference.abs() < ILL_CONDITIONING_THRESHOLD {
+            let midpoint = 0.5 * (input + self.second_previous_input);
+            let delta = midpoint - self.previous_input;
+            if delta.abs() < ILL_CONDITIONING_THRESHOLD {
+                self.function
+                    .evaluate(0.5 * (midpoint + self.previous_input))
+            } else {
+                2.0 / delta
+                    * (self.function.antiderivative(midpoint)
+                        + (self.function.second_antiderivative(self.previous_input)
+                            - self.function.second_antiderivative(midpoint))
+                            / delta)
+            }
+        } else {
+            2.0 / difference * (divided_difference - self.previous_divided_difference)
+        };
+
+        self.second_previous_input = self.previous_input;
+        self.previous_input = input;
+        self.previous_divided_difference = divided_difference;
+        output
+    }
+}
+
+impl<Function: ShapingFunction + Default> Processor for Waveshaper<Function> {
+    fn new(_: &u32, _: &usize) -> Self
+    where
+        Self: Sized,
+    {
+        let function = Function::default();
+        let previous_divided_difference = function.antiderivative(0.0);
+        Self {
+            antialiasing: Antialiasing::FirstOrder,
+            function,
+            previous_input: 0.0,
+            second_previous_input: 0.0,
+            previous_divided_difference,
+        }
+    }
+
+    #[inline(always)]
+    fn process(&mut self, data: &mut Vec<f32>, _context: &ProcessingContext) {
+        match self.antialiasing {
+            Antialiasing::FirstOrder => {
+                for sample in data.iter_mut() {
+                    *sample = self.process_first_order(*sample as f64) as f32;
+                }
+            }
+            Antialiasing::SecondOrder => {
+                for sample in data.iter_mut() {
+                    *sample = self.process_second_order(*sample as f64) as f32;
+                }
+            }
+        }
+    }
+}
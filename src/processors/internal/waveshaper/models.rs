@@ -0,0 +1,5 @@
+#[derive(Clone, Copy)]
+pub enum Antialiasing {
+    FirstOrder,
+    SecondOrder,
+}
@@ -1,9 +1,16 @@
 pub mod frontline;
 pub mod internal;
 
+/// Engine state shared with every processor, read-only while a block is processed
+#[derive(Clone, Copy)]
+pub struct ProcessingContext {
+    /// Beats per minute of the global transport
+    pub tempo: f32,
+}
+
 pub trait Processor {
     fn new(sample_rate: &u32, buffer_size: &usize) -> Self
     where
         Self: Sized;
-    fn process(&mut self, data: &mut Vec<f32>);
+    fn process(&mut self, data: &mut Vec<f32>, context: &ProcessingContext);
 }
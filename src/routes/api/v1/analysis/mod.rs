@@ -0,0 +1,86 @@
+use crate::managers::analysis::error::Error;
+use crate::managers::analysis::AnalysisManager;
+use crate::routes::api::v1::analysis::models::{
+    GetAnalysisSettingsResponse, GetScopeResponse, GetSpectrumResponse, SetAnalysisSettingsRequest,
+};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{get, put};
+use axum::{Extension, Json, Router};
+use futures::stream::{self, Stream};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::{interval, MissedTickBehavior};
+
+mod models;
+
+// Roughly 30 updates per second, the analyser itself doesn't refresh faster than 60
+static ANALYSIS_STREAM_PERIOD: Duration = Duration::from_millis(33);
+
+impl From<Error> for StatusCode {
+    fn from(_: Error) -> Self {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/settings", get(get_settings))
+        .route("/settings", put(set_settings))
+        .route("/spectrum", get(get_spectrum))
+        .route("/spectrum/stream", get(stream_spectrum))
+        .route("/scope", get(get_scope))
+        .route("/scope/stream", get(stream_scope))
+}
+
+async fn get_settings(
+    analysis_manager: Extension<AnalysisManager>,
+) -> Json<GetAnalysisSettingsResponse> {
+    Json(analysis_manager.get_settings())
+}
+
+async fn set_settings(
+    analysis_manager: Extension<AnalysisManager>,
+    Json(payload): Json<SetAnalysisSettingsRequest>,
+) -> Result<(), StatusCode> {
+    Ok(analysis_manager.set_settings(payload)?)
+}
+
+async fn get_spectrum(analysis_manager: Extension<AnalysisManager>) -> Json<GetSpectrumResponse> {
+    Json(analysis_manager.get_spectrum())
+}
+
+async fn get_scope(analysis_manager: Extension<AnalysisManager>) -> Json<GetScopeResponse> {
+    Json(analysis_manager.get_scope())
+}
+
+async fn stream_spectrum(
+    analysis_manager: Extension<AnalysisManager>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    create_stream(analysis_manager.0, |manager| manager.get_spectrum())
+}
+
+async fn stream_scope(
+    analysis_manager: Extension<AnalysisManager>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    create_stream(analysis_manager.0, |manager| manager.get_scope())
+}
+
+fn create_stream<Data: Serialize + 'static>(
+    analysis_manager: AnalysisManager,
+    read: fn(&AnalysisManager) -> Data,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let mut ticker = interval(ANALYSIS_STREAM_PERIOD);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let stream = stream::unfold(
+        (ticker, analysis_manager),
+        move |(mut ticker, analysis_manager)| async move {
+            ticker.tick().await;
+            let event = Event::default().json_data(read(&analysis_manager));
+            Some((event, (ticker, analysis_manager)))
+        },
+    );
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
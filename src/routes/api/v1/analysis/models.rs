@@ -0,0 +1,9 @@
+use crate::managers::analysis::models::{AnalysisSettings, Scope, Spectrum};
+
+pub type GetSpectrumResponse = Spectrum;
+
+pub type GetScopeResponse = Scope;
+
+pub type GetAnalysisSettingsResponse = AnalysisSettings;
+
+pub type SetAnalysisSettingsRequest = AnalysisSettings;
@@ -0,0 +1,52 @@
+use crate::managers::dsp::audio_config::{get_audio_hosts, save_audio_config};
+use crate::managers::dsp::models::AudioConfig;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::managers::file_system::FileSystemManager;
+use crate::routes::api::v1::audio::models::GetDevicesResponse;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use std::sync::{Arc, Mutex};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/devices", get(get_devices))
+        .route("/config", get(get_config).put(set_config))
+}
+
+async fn get_devices() -> Json<GetDevicesResponse> {
+    Json(GetDevicesResponse {
+        hosts: get_audio_hosts(),
+    })
+}
+
+async fn get_config(dsp_manager: Extension<Arc<Mutex<DSPManager>>>) -> Json<AudioConfig> {
+    Json(dsp_manager.lock().unwrap().get_audio_config())
+}
+
+/// Rebuilds the audio streams live and saves the config for the next start
+async fn set_config(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+    file_system_manager: Extension<FileSystemManager>,
+    Json(payload): Json<AudioConfig>,
+) -> Result<Json<AudioConfig>, StatusCode> {
+    if !payload.is_valid() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    // Rebuilding the streams blocks on the devices and the output fade
+    let audio_config = payload.clone();
+    let dsp_manager = dsp_manager.0.clone();
+    tokio::task::spawn_blocking(move || dsp_manager.lock().unwrap().reconfigure(&audio_config))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+    save_audio_config(&file_system_manager, &payload)?;
+    events_manager.publish(StateEvent::AudioConfigChanged {
+        config: payload.clone(),
+    });
+    Ok(Json(payload))
+}
@@ -0,0 +1,7 @@
+use crate::managers::dsp::models::AudioHost;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GetDevicesResponse {
+    pub hosts: Vec<AudioHost>,
+}
@@ -1,4 +1,6 @@
 use crate::managers::audio_device_settings::AudioDeviceSettingsManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
 use crate::routes::api::v1::device::models::{
     GetSettingsResponse, SetInputGainRequest, SetMicBiasRequest, SetVolumeRequest,
 };
@@ -32,27 +34,37 @@ async fn get_settings(
 
 async fn set_input_gain(
     audio_device_settings_manager: Extension<Arc<AudioDeviceSettingsManager>>,
+    events_manager: Extension<EventsManager>,
     Json(payload): Json<SetInputGainRequest>,
 ) {
     audio_device_settings_manager
         .set_input_gain(payload.value)
         .unwrap();
+    events_manager.publish(StateEvent::InputGainChanged {
+        value: payload.value,
+    });
 }
 
 async fn set_volume(
     audio_device_settings_manager: Extension<Arc<AudioDeviceSettingsManager>>,
+    events_manager: Extension<EventsManager>,
     Json(payload): Json<SetVolumeRequest>,
 ) {
     audio_device_settings_manager
         .set_volume(payload.value)
         .unwrap();
+    events_manager.publish(StateEvent::VolumeChanged {
+        value: payload.value,
+    });
 }
 
 async fn set_mic_bias(
     audio_device_settings_manager: Extension<Arc<AudioDeviceSettingsManager>>,
+    events_manager: Extension<EventsManager>,
     Json(payload): Json<SetMicBiasRequest>,
 ) {
     audio_device_settings_manager
         .set_mic_bias(payload.on)
         .unwrap();
+    events_manager.publish(StateEvent::MicBiasChanged { on: payload.on });
 }
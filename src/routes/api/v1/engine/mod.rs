@@ -0,0 +1,61 @@
+use crate::managers::dsp::error::Error as DSPError;
+use crate::managers::dsp::{stop_gracefully, DSPManager};
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::routes::api::v1::engine::models::GetEngineStateResponse;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use std::sync::{Arc, Mutex};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_engine_state))
+        .route("/start", post(start_engine))
+        .route("/stop", post(stop_engine))
+}
+
+async fn get_engine_state(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+) -> Json<GetEngineStateResponse> {
+    Json(GetEngineStateResponse {
+        is_running: dsp_manager.lock().unwrap().is_running(),
+    })
+}
+
+async fn start_engine(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+) -> Result<(), StatusCode> {
+    dsp_manager.lock().unwrap().start()?;
+    events_manager.publish(StateEvent::EngineStateChanged { is_running: true });
+    Ok(())
+}
+
+/// Fades the output out and pauses audio processing, the chain is kept as is
+async fn stop_engine(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+) -> Result<(), StatusCode> {
+    stop_gracefully(&dsp_manager).await?;
+    let is_running = dsp_manager.lock().unwrap().is_running();
+    events_manager.publish(StateEvent::EngineStateChanged { is_running });
+    Ok(())
+}
+
+impl From<DSPError> for StatusCode {
+    fn from(value: DSPError) -> Self {
+        tracing::error!("audio engine error: {:?}", value);
+        match value {
+            DSPError::DefaultDeviceNotFound | DSPError::NamedDeviceNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            // The device rejected the requested channels, sample rate or buffer size
+            DSPError::BuildStream => StatusCode::UNPROCESSABLE_ENTITY,
+            DSPError::StreamsClosed => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
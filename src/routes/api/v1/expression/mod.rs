@@ -0,0 +1,76 @@
+use crate::managers::dsp::error::TransformProcessorError;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::routes::api::v1::expression::models::{
+    CreateExpressionAssignmentRequest, GetExpressionResponse, SetExpressionPositionRequest,
+};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+use std::sync::{Arc, Mutex};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_expression))
+        .route("/", put(set_expression_position))
+        .route("/assignments", post(create_expression_assignment))
+        .route(
+            "/assignments/:assignment_index",
+            delete(delete_expression_assignment),
+        )
+}
+
+async fn get_expression(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+) -> Json<GetExpressionResponse> {
+    let dsp_manager = dsp_manager.lock().unwrap();
+    Json(GetExpressionResponse {
+        position: dsp_manager.get_expression_position(),
+        assignments: dsp_manager.get_expression_assignments(),
+    })
+}
+
+async fn set_expression_position(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+    Json(payload): Json<SetExpressionPositionRequest>,
+) {
+    let position = {
+        let mut dsp_manager = dsp_manager.lock().unwrap();
+        dsp_manager.set_expression_position(payload.position);
+        dsp_manager.get_expression_position()
+    };
+    events_manager.publish(StateEvent::ExpressionChanged { position });
+}
+
+async fn create_expression_assignment(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    Json(payload): Json<CreateExpressionAssignmentRequest>,
+) -> Result<(), StatusCode> {
+    match dsp_manager
+        .lock()
+        .unwrap()
+        .add_expression_assignment(payload)
+    {
+        Ok(_) => Ok(()),
+        Err(TransformProcessorError::NotFound) => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn delete_expression_assignment(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    Path(assignment_index): Path<usize>,
+) -> Result<(), StatusCode> {
+    match dsp_manager
+        .lock()
+        .unwrap()
+        .remove_expression_assignment(assignment_index)
+    {
+        Ok(_) => Ok(()),
+        Err(TransformProcessorError::NotFound) => Err(StatusCode::NOT_FOUND),
+    }
+}
@@ -0,0 +1,15 @@
+use crate::managers::dsp::models::ExpressionAssignment;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GetExpressionResponse {
+    pub position: f32,
+    pub assignments: Vec<ExpressionAssignment>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SetExpressionPositionRequest {
+    pub position: f32,
+}
+
+pub type CreateExpressionAssignmentRequest = ExpressionAssignment;
@@ -0,0 +1,40 @@
+use crate::managers::dsp::DSPManager;
+use crate::routes::api::v1::meters::models::GetMetersResponse;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use futures::stream::{self, Stream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{interval, MissedTickBehavior};
+
+mod models;
+
+// Roughly 30 updates per second, enough for smooth meters in the UI
+static METERS_STREAM_PERIOD: Duration = Duration::from_millis(33);
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_meters))
+        .route("/stream", get(stream_meters))
+}
+
+async fn get_meters(dsp_manager: Extension<Arc<Mutex<DSPManager>>>) -> Json<GetMetersResponse> {
+    Json(dsp_manager.lock().unwrap().get_meters().get_readings())
+}
+
+async fn stream_meters(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let meters = dsp_manager.lock().unwrap().get_meters();
+    let mut ticker = interval(METERS_STREAM_PERIOD);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let stream = stream::unfold((ticker, meters), |(mut ticker, meters)| async move {
+        ticker.tick().await;
+        let event = Event::default().json_data(meters.get_readings());
+        Some((event, (ticker, meters)))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
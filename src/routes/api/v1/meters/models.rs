@@ -0,0 +1,3 @@
+use crate::managers::dsp::models::MetersReadings;
+
+pub type GetMetersResponse = MetersReadings;
@@ -0,0 +1,76 @@
+use crate::managers::midi::error::Error;
+use crate::managers::midi::MidiManager;
+use crate::routes::api::v1::midi::models::{
+    CreateMappingRequest, GetLearnResponse, GetMappingsResponse, GetPortsResponse,
+    StartLearningRequest,
+};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Extension, Json, Router};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/ports", get(get_ports))
+        .route("/mappings", get(get_mappings))
+        .route("/mappings", post(create_mapping))
+        .route("/mappings/:mapping_index", delete(delete_mapping))
+        .route("/learn", get(get_learn))
+        .route("/learn", post(start_learning))
+        .route("/learn", delete(stop_learning))
+}
+
+async fn get_ports(midi_manager: Extension<MidiManager>) -> Json<GetPortsResponse> {
+    Json(GetPortsResponse {
+        ports: midi_manager.get_port_names(),
+    })
+}
+
+async fn get_mappings(midi_manager: Extension<MidiManager>) -> Json<GetMappingsResponse> {
+    Json(GetMappingsResponse {
+        mappings: midi_manager.get_mappings(),
+    })
+}
+
+async fn create_mapping(
+    midi_manager: Extension<MidiManager>,
+    Json(payload): Json<CreateMappingRequest>,
+) -> Result<(), StatusCode> {
+    Ok(midi_manager.add_mapping(payload).await?)
+}
+
+async fn delete_mapping(
+    midi_manager: Extension<MidiManager>,
+    Path(mapping_index): Path<usize>,
+) -> Result<(), StatusCode> {
+    Ok(midi_manager.remove_mapping(mapping_index).await?)
+}
+
+async fn get_learn(midi_manager: Extension<MidiManager>) -> Json<GetLearnResponse> {
+    Json(GetLearnResponse {
+        target: midi_manager.get_learn_target(),
+    })
+}
+
+async fn start_learning(
+    midi_manager: Extension<MidiManager>,
+    Json(payload): Json<StartLearningRequest>,
+) {
+    midi_manager.start_learning(payload.target);
+}
+
+async fn stop_learning(midi_manager: Extension<MidiManager>) {
+    midi_manager.stop_learning();
+}
+
+impl From<Error> for StatusCode {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::InvalidMapping => StatusCode::BAD_REQUEST,
+            Error::MappingNotFound => StatusCode::NOT_FOUND,
+            Error::Init | Error::Connect => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::managers::midi::models::{MidiMapping, MidiTarget};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GetPortsResponse {
+    pub ports: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetMappingsResponse {
+    pub mappings: Vec<MidiMapping>,
+}
+
+pub type CreateMappingRequest = MidiMapping;
+
+#[derive(Serialize, Deserialize)]
+pub struct GetLearnResponse {
+    pub target: Option<MidiTarget>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StartLearningRequest {
+    pub target: MidiTarget,
+}
@@ -1,10 +1,19 @@
 use axum::Router;
 
+mod analysis;
+mod audio;
 mod device;
+mod engine;
+mod expression;
 mod healthy;
+mod meters;
+mod midi;
 mod performance;
 mod presets;
 mod processors;
+mod setlists;
+mod tempo;
+mod ws;
 
 pub fn create_router() -> Router {
     // TODO: add "/capture" and "/processing" routes
@@ -14,4 +23,13 @@ pub fn create_router() -> Router {
         .nest("/presets", presets::create_router())
         .nest("/processors", processors::create_router())
         .nest("/device", device::create_router())
+        .nest("/expression", expression::create_router())
+        .nest("/ws", ws::create_router())
+        .nest("/meters", meters::create_router())
+        .nest("/analysis", analysis::create_router())
+        .nest("/midi", midi::create_router())
+        .nest("/tempo", tempo::create_router())
+        .nest("/setlists", setlists::create_router())
+        .nest("/engine", engine::create_router())
+        .nest("/audio", audio::create_router())
 }
@@ -1,21 +1,35 @@
 use crate::managers::cache::CacheManager;
-use crate::managers::database::models::Preset;
+use crate::managers::database::migrations::PRESET_SCHEMA_VERSION;
+use crate::managers::database::models::{Preset, PresetMetadata, PresetQuery};
 use crate::managers::database::DatabaseManager;
+use crate::managers::dsp::transport::DEFAULT_TEMPO;
 use crate::managers::dsp::DSPManager;
-use crate::processors::frontline::create_processor_from_type;
-use crate::processors::frontline::models::ParameterValue;
-use crate::routes::api::v1::presets::models::requests::SaveCurrentPresetRequest;
+use crate::managers::file_system::FileSystemManager;
+use crate::managers::presets::bundle::{create_bundle, open_bundle};
+use crate::managers::presets::error::{BundleError, RevisionError};
+use crate::managers::presets::revisions::diff_presets;
+use crate::managers::presets::{get_timestamp, PresetsManager};
+use crate::routes::api::v1::presets::models::requests::{
+    DiffPresetRevisionsQuery, DuplicatePresetRequest, ImportPresetRequest,
+    SaveCurrentPresetAsRequest, SaveCurrentPresetRequest, UpdatePresetRequest,
+};
 use crate::routes::api::v1::presets::models::responses::{
-    CreateNewPresetResponse, GetCurrentPresetResponse, GetDefaultPresetIdResponse,
-    GetPresetsResponse, LoadPresetResponse, PresetBasicInfo,
+    CreateNewPresetResponse, DiffPresetRevisionsResponse, DuplicatePresetResponse,
+    ExportPresetResponse, GetCurrentPresetResponse, GetDefaultPresetIdResponse,
+    GetPresetRevisionsResponse, GetPresetsResponse, ImportPresetResponse, LoadPresetResponse,
+    PresetBasicInfo, PresetRevisionInfo, RestorePresetRevisionResponse,
+    RevertCurrentPresetResponse, SaveCurrentPresetAsResponse, UpdatePresetResponse,
 };
-use axum::extract::Path;
-use axum::routing::{delete, get, post};
+use axum::extract::{Path, Query};
+use axum::http::header::{ETAG, IF_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::routing::{delete, get, patch, post};
 use axum::{Extension, Json, Router};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 mod models;
+mod scenes;
 
 pub fn create_router() -> Router {
     Router::new()
@@ -26,7 +40,20 @@ pub fn create_router() -> Router {
         .route("/current", get(get_current_preset))
         .route("/current", post(save_current_preset))
         .route("/current", delete(delete_current_preset))
-        .route("/<preset_id>/load", post(load_preset))
+        .route("/current/save-as", post(save_current_preset_as))
+        .route("/current/revert", post(revert_current_preset))
+        .route("/import", post(import_preset))
+        .route("/:preset_id/load", post(load_preset))
+        .route("/:preset_id/export", get(export_preset))
+        .route("/:preset_id", patch(update_preset))
+        .route("/:preset_id/duplicate", post(duplicate_preset))
+        .route("/:preset_id/revisions", get(get_preset_revisions))
+        .route("/:preset_id/revisions/diff", get(diff_preset_revisions))
+        .route(
+            "/:preset_id/revisions/:revision_id/restore",
+            post(restore_preset_revision),
+        )
+        .nest("/current/scenes", scenes::create_router())
 }
 
 async fn get_default_preset_id(
@@ -50,62 +77,134 @@ async fn set_default_preset_id(
 }
 
 async fn create_new_preset(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
-    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    presets_manager: Extension<PresetsManager>,
 ) -> Json<CreateNewPresetResponse> {
-    let id = reset_current_preset(dsp_manager, cache_manager);
+    let id = presets_manager.reset_current_preset();
 
     Json(CreateNewPresetResponse { id })
 }
 
-async fn get_presets(database_manager: Extension<DatabaseManager>) -> Json<GetPresetsResponse> {
+async fn get_presets(
+    Query(query): Query<PresetQuery>,
+    database_manager: Extension<DatabaseManager>,
+) -> Json<GetPresetsResponse> {
+    let page = database_manager.search_presets(&query).await;
     Json(GetPresetsResponse {
-        presets: database_manager
-            .get_presets_list()
-            .await
-            .iter()
+        presets: page
+            .presets
+            .into_iter()
             .map(|preset| PresetBasicInfo {
                 id: preset.id,
-                name: preset.name.clone(),
+                name: preset.name,
+                metadata: preset.metadata,
             })
             .collect(),
+        total: page.total,
     })
 }
 
+/// The ETag header is set when the current preset is stored, to be sent back as If-Match
 async fn get_current_preset(
     dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
     database_manager: Extension<DatabaseManager>,
     cache_manager: Extension<Arc<Mutex<CacheManager>>>,
-) -> Json<GetCurrentPresetResponse> {
-    let current_preset =
-        get_current_preset_info(dsp_manager, database_manager, cache_manager).await;
-    Json(GetCurrentPresetResponse {
-        preset: current_preset,
-    })
+) -> (HeaderMap, Json<GetCurrentPresetResponse>) {
+    let Some(saved_preset) = get_saved_current_preset(&database_manager, &cache_manager).await
+    else {
+        return (
+            HeaderMap::new(),
+            Json(GetCurrentPresetResponse {
+                preset: None,
+                is_modified: false,
+                changes: None,
+            }),
+        );
+    };
+    // Presets never saved have no version yet
+    let headers = if saved_preset.metadata.modified_at > 0 {
+        get_etag_headers(&saved_preset)
+    } else {
+        HeaderMap::new()
+    };
+    let current_preset = apply_live_state(saved_preset.clone(), &dsp_manager, &cache_manager);
+    let changes = diff_presets(&saved_preset, &current_preset);
+    (
+        headers,
+        Json(GetCurrentPresetResponse {
+            preset: Some(current_preset),
+            is_modified: !changes.is_empty(),
+            changes: Some(changes),
+        }),
+    )
 }
 
+/// Reloads the current preset as last saved, dropping any unsaved change
+async fn revert_current_preset(
+    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    presets_manager: Extension<PresetsManager>,
+) -> Result<(HeaderMap, Json<RevertCurrentPresetResponse>), StatusCode> {
+    let current_preset_id = { cache_manager.lock().unwrap().current_preset_id };
+    let preset = presets_manager
+        .load_preset(current_preset_id.ok_or(StatusCode::NOT_FOUND)?)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((get_etag_headers(&preset), Json(preset)))
+}
+
+/// Fails with 412 when If-Match doesn't match the stored preset, i.e. another client saved it
 async fn save_current_preset(
     dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
     database_manager: Extension<DatabaseManager>,
     cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    presets_manager: Extension<PresetsManager>,
+    headers: HeaderMap,
     Json(payload): Json<SaveCurrentPresetRequest>,
-) {
+) -> Result<HeaderMap, StatusCode> {
     let current_preset_id = { cache_manager.lock().unwrap().current_preset_id };
-    if let Some(current_preset_id) = current_preset_id {
-        let current_preset =
-            get_current_preset_info(dsp_manager, database_manager.clone(), cache_manager).await;
-        if let Some(mut current_preset) = current_preset {
-            current_preset.name = payload.name;
-            database_manager.delete_preset(current_preset_id).await;
-            database_manager.save_preset(current_preset).await;
-        }
-    }
+    let stored_preset = match current_preset_id {
+        Some(id) => database_manager.get_preset(id).await,
+        None => None,
+    };
+    let expected_version = get_expected_version(&headers, stored_preset.as_ref())?;
+    let current_preset =
+        get_current_preset_info(dsp_manager, database_manager, cache_manager).await;
+    let Some(mut current_preset) = current_preset else {
+        return Ok(HeaderMap::new());
+    };
+    current_preset.name = payload.name;
+    current_preset.metadata.version = expected_version;
+    let revision = presets_manager
+        .save_preset(current_preset, payload.message)
+        .await?;
+    Ok(get_etag_headers(&revision.preset))
 }
 
-async fn delete_current_preset(
+async fn save_current_preset_as(
     dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
     database_manager: Extension<DatabaseManager>,
     cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    presets_manager: Extension<PresetsManager>,
+    Json(payload): Json<SaveCurrentPresetAsRequest>,
+) -> Result<(HeaderMap, Json<SaveCurrentPresetAsResponse>), StatusCode> {
+    let mut current_preset = get_current_preset_info(dsp_manager, database_manager, cache_manager)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    current_preset.name = payload.name;
+    let revision = presets_manager
+        .save_preset_as(current_preset, payload.message)
+        .await?;
+    Ok((
+        get_etag_headers(&revision.preset),
+        Json(SaveCurrentPresetAsResponse {
+            id: revision.preset_id,
+        }),
+    ))
+}
+
+async fn delete_current_preset(
+    database_manager: Extension<DatabaseManager>,
+    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    presets_manager: Extension<PresetsManager>,
 ) {
     let current_preset_id = { cache_manager.lock().unwrap().current_preset_id };
     let default_preset_id = database_manager.get_default_preset_id().await;
@@ -123,71 +222,214 @@ async fn delete_current_preset(
         }
     }
 
-    let _ = reset_current_preset(dsp_manager, cache_manager);
+    let _ = presets_manager.reset_current_preset();
 }
 
 async fn load_preset(
     Path(preset_id): Path<Uuid>,
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    presets_manager: Extension<PresetsManager>,
+) -> (HeaderMap, Json<LoadPresetResponse>) {
+    let preset = presets_manager.load_preset(preset_id).await;
+    let headers = preset.as_ref().map(get_etag_headers).unwrap_or_default();
+    (headers, Json(preset))
+}
+
+async fn export_preset(
+    Path(preset_id): Path<Uuid>,
     database_manager: Extension<DatabaseManager>,
-    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
-) -> Json<LoadPresetResponse> {
-    if let Some(preset) = database_manager.get_preset(preset_id).await {
-        let mut dsp_manager = dsp_manager.lock().unwrap();
-        cache_manager.lock().unwrap().current_preset_id = Some(preset_id);
-
-        dsp_manager.clear_all_processors();
-        preset
-            .processors
-            .iter()
-            .enumerate()
-            .for_each(|(index, processor_info)| {
-                let mut processor = create_processor_from_type(
-                    &processor_info.processor_type,
-                    &dsp_manager.sample_rate,
-                    &dsp_manager.buffer_size,
-                );
-                processor_info
-                    .parameters
-                    .numeric
-                    .iter()
-                    .for_each(|(parameter, value)| {
-                        processor.set_parameter(*parameter, ParameterValue::Numeric(*value))
-                    });
-                processor_info
-                    .parameters
-                    .string
-                    .iter()
-                    .for_each(|(parameter, value)| {
-                        processor.set_parameter(*parameter, ParameterValue::String(value.clone()))
-                    });
-                dsp_manager.add_processor(index, processor);
-                let _ = dsp_manager.transform_processor_settings(index, |settings| {
-                    settings.bypassed = processor_info.settings.bypassed;
-                });
-            });
-
-        Json(Some(preset))
-    } else {
-        Json(None)
+    file_system_manager: Extension<FileSystemManager>,
+) -> Result<Json<ExportPresetResponse>, StatusCode> {
+    let preset = database_manager
+        .get_preset(preset_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(create_bundle(preset, &file_system_manager)))
+}
+
+async fn import_preset(
+    file_system_manager: Extension<FileSystemManager>,
+    presets_manager: Extension<PresetsManager>,
+    Json(payload): Json<ImportPresetRequest>,
+) -> Result<Json<ImportPresetResponse>, StatusCode> {
+    let imported_preset = open_bundle(payload, &file_system_manager)?;
+    let id = imported_preset.preset.id;
+    presets_manager
+        .save_preset(imported_preset.preset, Some("Imported".to_string()))
+        .await?;
+    Ok(Json(ImportPresetResponse {
+        id,
+        missing_assets: imported_preset.missing_assets,
+    }))
+}
+
+/// Renames the preset or edits its metadata. They don't affect the sound, so no revision is
+/// created.
+async fn update_preset(
+    Path(preset_id): Path<Uuid>,
+    database_manager: Extension<DatabaseManager>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdatePresetRequest>,
+) -> Result<(HeaderMap, Json<UpdatePresetResponse>), StatusCode> {
+    let mut preset = database_manager
+        .get_preset(preset_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let expected_version = get_expected_version(&headers, Some(&preset))?;
+    let metadata = &mut preset.metadata;
+    if let Some(name) = payload.name {
+        preset.name = name;
+    }
+    if let Some(tags) = payload.tags {
+        metadata.tags = tags;
+    }
+    if let Some(author) = payload.author {
+        metadata.author = Some(author).filter(|author| !author.is_empty());
     }
+    if let Some(description) = payload.description {
+        metadata.description = Some(description).filter(|description| !description.is_empty());
+    }
+    if let Some(is_favourite) = payload.is_favourite {
+        metadata.is_favourite = is_favourite;
+    }
+    if let Some(guitar_notes) = payload.guitar_notes {
+        metadata.guitar_notes = Some(guitar_notes).filter(|guitar_notes| !guitar_notes.is_empty());
+    }
+    metadata.modified_at = get_timestamp();
+    metadata.version = expected_version + 1;
+    database_manager
+        .save_preset(preset.clone(), expected_version)
+        .await?;
+    Ok((
+        get_etag_headers(&preset),
+        Json(PresetBasicInfo {
+            id: preset.id,
+            name: preset.name,
+            metadata: preset.metadata,
+        }),
+    ))
 }
 
-fn reset_current_preset(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
-    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
-) -> Uuid {
-    let id = Uuid::new_v4();
-    cache_manager.lock().unwrap().current_preset_id = Some(id);
-    dsp_manager.lock().unwrap().clear_all_processors();
+async fn duplicate_preset(
+    Path(preset_id): Path<Uuid>,
+    database_manager: Extension<DatabaseManager>,
+    presets_manager: Extension<PresetsManager>,
+    Json(payload): Json<DuplicatePresetRequest>,
+) -> Result<Json<DuplicatePresetResponse>, StatusCode> {
+    let mut preset = database_manager
+        .get_preset(preset_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    preset.id = Uuid::new_v4();
+    preset.is_default = false;
+    preset.name = payload
+        .name
+        .unwrap_or_else(|| format!("{} (copy)", preset.name));
+    preset.metadata.created_at = 0;
+    preset.metadata.is_favourite = false;
+    preset.metadata.version = 0;
+    let id = preset.id;
+    presets_manager
+        .save_preset(preset, Some(format!("Duplicated from {}", preset_id)))
+        .await?;
+    Ok(Json(DuplicatePresetResponse { id }))
+}
+
+async fn get_preset_revisions(
+    Path(preset_id): Path<Uuid>,
+    database_manager: Extension<DatabaseManager>,
+) -> Json<GetPresetRevisionsResponse> {
+    Json(GetPresetRevisionsResponse {
+        revisions: database_manager
+            .get_preset_revisions(preset_id)
+            .await
+            .into_iter()
+            .map(|revision| PresetRevisionInfo {
+                id: revision.id,
+                timestamp: revision.timestamp,
+                message: revision.message,
+            })
+            .collect(),
+    })
+}
+
+async fn diff_preset_revisions(
+    Path(preset_id): Path<Uuid>,
+    Query(query): Query<DiffPresetRevisionsQuery>,
+    database_manager: Extension<DatabaseManager>,
+) -> Result<Json<DiffPresetRevisionsResponse>, StatusCode> {
+    let mut revisions = vec![];
+    for revision_id in [query.from, query.to] {
+        let revision = database_manager
+            .get_preset_revision(revision_id)
+            .await
+            .filter(|revision| revision.preset_id == preset_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        revisions.push(revision);
+    }
+    Ok(Json(diff_presets(
+        &revisions[0].preset,
+        &revisions[1].preset,
+    )))
+}
 
-    id
+async fn restore_preset_revision(
+    Path((preset_id, revision_id)): Path<(Uuid, Uuid)>,
+    presets_manager: Extension<PresetsManager>,
+) -> Result<Json<RestorePresetRevisionResponse>, StatusCode> {
+    let preset = presets_manager
+        .restore_preset_revision(preset_id, revision_id)
+        .await?;
+    Ok(Json(preset))
+}
+
+/// The preset version, which changes whenever it is saved or edited
+fn get_etag_headers(preset: &Preset) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", preset.metadata.version)) {
+        headers.insert(ETAG, etag);
+    }
+    headers
+}
+
+/// The version the client's changes are based on, taken from If-Match. It is only checked
+/// here to fail early: the store checks it again when writing, so a concurrent save still
+/// fails. Without If-Match, the changes are based on whatever is stored now.
+fn get_expected_version(
+    headers: &HeaderMap,
+    stored_preset: Option<&Preset>,
+) -> Result<u64, StatusCode> {
+    let stored_version = stored_preset.map(|preset| preset.metadata.version);
+    let Some(if_match) = headers.get(IF_MATCH) else {
+        return Ok(stored_version.unwrap_or_default());
+    };
+    let stored_version = stored_version.ok_or(StatusCode::PRECONDITION_FAILED)?;
+    if if_match == "*" {
+        return Ok(stored_version);
+    }
+    let version = if_match
+        .to_str()
+        .ok()
+        .and_then(|if_match| if_match.trim_matches('"').parse::<u64>().ok());
+    match version {
+        Some(version) if version == stored_version => Ok(version),
+        _ => Err(StatusCode::PRECONDITION_FAILED),
+    }
 }
 
 async fn get_current_preset_info(
     dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
     database_manager: Extension<DatabaseManager>,
     cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+) -> Option<Preset> {
+    get_saved_current_preset(&database_manager, &cache_manager)
+        .await
+        .map(|preset| apply_live_state(preset, &dsp_manager, &cache_manager))
+}
+
+/// The current preset as last saved, or an empty one if it has never been saved
+async fn get_saved_current_preset(
+    database_manager: &DatabaseManager,
+    cache_manager: &Mutex<CacheManager>,
 ) -> Option<Preset> {
     let current_preset_id = { cache_manager.lock().unwrap().current_preset_id };
     let preset_from_db = if let Some(id) = current_preset_id {
@@ -195,15 +437,50 @@ async fn get_current_preset_info(
     } else {
         None
     };
-    preset_from_db
-        .or(current_preset_id.map(|id| Preset {
-            id,
-            is_default: false,
-            name: "".to_string(),
-            processors: vec![],
-        }))
-        .map(|mut preset| {
-            preset.processors = dsp_manager.lock().unwrap().get_processors_info();
-            preset
-        })
+    preset_from_db.or(current_preset_id.map(|id| Preset {
+        schema_version: PRESET_SCHEMA_VERSION,
+        id,
+        is_default: false,
+        name: "".to_string(),
+        processors: vec![],
+        tempo: DEFAULT_TEMPO,
+        scenes: vec![],
+        expression_assignments: vec![],
+        metadata: PresetMetadata::default(),
+    }))
+}
+
+fn apply_live_state(
+    mut preset: Preset,
+    dsp_manager: &Mutex<DSPManager>,
+    cache_manager: &Mutex<CacheManager>,
+) -> Preset {
+    preset.scenes = cache_manager.lock().unwrap().current_scenes.clone();
+    let dsp_manager = dsp_manager.lock().unwrap();
+    preset.processors = dsp_manager.get_processors_info();
+    preset.expression_assignments = dsp_manager.get_expression_assignments();
+    preset.tempo = dsp_manager.get_tempo();
+    preset
+}
+
+impl From<BundleError> for StatusCode {
+    fn from(value: BundleError) -> Self {
+        match value {
+            BundleError::FileSystem => StatusCode::INTERNAL_SERVER_ERROR,
+            BundleError::InvalidAsset => StatusCode::UNPROCESSABLE_ENTITY,
+            BundleError::InvalidParameter => StatusCode::UNPROCESSABLE_ENTITY,
+            BundleError::InvalidPreset => StatusCode::UNPROCESSABLE_ENTITY,
+            BundleError::UnsupportedProcessor => StatusCode::UNPROCESSABLE_ENTITY,
+            BundleError::UnsupportedSchemaVersion => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+}
+
+impl From<RevisionError> for StatusCode {
+    fn from(value: RevisionError) -> Self {
+        match value {
+            RevisionError::Database => StatusCode::INTERNAL_SERVER_ERROR,
+            RevisionError::NotFound => StatusCode::NOT_FOUND,
+        }
+    }
 }
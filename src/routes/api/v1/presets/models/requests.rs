@@ -1,6 +1,43 @@
+use crate::managers::presets::bundle::PresetBundle;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize)]
 pub struct SaveCurrentPresetRequest {
     pub name: String,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+pub type ImportPresetRequest = PresetBundle;
+
+#[derive(Serialize, Deserialize)]
+pub struct DiffPresetRevisionsQuery {
+    pub from: Uuid,
+    pub to: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveCurrentPresetAsRequest {
+    pub name: String,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DuplicatePresetRequest {
+    /// Defaults to the original name followed by "(copy)"
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Only the given fields are changed, empty strings clear the optional ones
+#[derive(Serialize, Deserialize)]
+pub struct UpdatePresetRequest {
+    pub name: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub is_favourite: Option<bool>,
+    pub guitar_notes: Option<String>,
 }
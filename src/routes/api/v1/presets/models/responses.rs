@@ -1,4 +1,6 @@
-use crate::managers::database::models::Preset;
+use crate::managers::database::models::{Preset, PresetMetadata};
+use crate::managers::presets::bundle::PresetBundle;
+use crate::managers::presets::revisions::PresetDiff;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,16 +18,61 @@ pub struct CreateNewPresetResponse {
 pub struct PresetBasicInfo {
     pub id: Uuid,
     pub name: String,
+    pub metadata: PresetMetadata,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct GetPresetsResponse {
     pub presets: Vec<PresetBasicInfo>,
+    /// Matching presets, regardless of offset and limit
+    pub total: usize,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct GetCurrentPresetResponse {
     pub preset: Option<Preset>,
+    /// Whether the live chain diverges from the saved preset
+    pub is_modified: bool,
+    /// From the saved preset to the live chain
+    pub changes: Option<PresetDiff>,
 }
 
 pub type LoadPresetResponse = Option<Preset>;
+
+pub type RevertCurrentPresetResponse = Preset;
+
+pub type ExportPresetResponse = PresetBundle;
+
+#[derive(Serialize, Deserialize)]
+pub struct ImportPresetResponse {
+    pub id: Uuid,
+    pub missing_assets: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PresetRevisionInfo {
+    pub id: Uuid,
+    pub timestamp: u64,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetPresetRevisionsResponse {
+    pub revisions: Vec<PresetRevisionInfo>,
+}
+
+pub type DiffPresetRevisionsResponse = PresetDiff;
+
+pub type RestorePresetRevisionResponse = Preset;
+
+pub type UpdatePresetResponse = PresetBasicInfo;
+
+#[derive(Serialize, Deserialize)]
+pub struct DuplicatePresetResponse {
+    pub id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveCurrentPresetAsResponse {
+    pub id: Uuid,
+}
@@ -0,0 +1,82 @@
+use crate::managers::presets::error::SceneError;
+use crate::managers::presets::PresetsManager;
+use crate::routes::api::v1::presets::scenes::models::{
+    CreateSceneRequest, CreateSceneResponse, GetScenesResponse, RenameSceneRequest, SceneBasicInfo,
+};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_scenes))
+        .route("/", post(create_scene))
+        .route("/:scene_index", put(save_scene))
+        .route("/:scene_index", delete(delete_scene))
+        .route("/:scene_index/name", put(rename_scene))
+        .route("/:scene_index/select", post(select_scene))
+}
+
+async fn get_scenes(presets_manager: Extension<PresetsManager>) -> Json<GetScenesResponse> {
+    let (scenes, current_scene_index) = presets_manager.get_scenes();
+    Json(GetScenesResponse {
+        scenes: scenes
+            .into_iter()
+            .enumerate()
+            .map(|(index, scene)| SceneBasicInfo {
+                index,
+                name: scene.name,
+            })
+            .collect(),
+        current_scene_index,
+    })
+}
+
+async fn create_scene(
+    presets_manager: Extension<PresetsManager>,
+    Json(payload): Json<CreateSceneRequest>,
+) -> Json<CreateSceneResponse> {
+    Json(CreateSceneResponse {
+        index: presets_manager.create_scene(payload.name),
+    })
+}
+
+async fn save_scene(
+    presets_manager: Extension<PresetsManager>,
+    Path(scene_index): Path<usize>,
+) -> Result<(), StatusCode> {
+    Ok(presets_manager.save_scene(scene_index)?)
+}
+
+async fn delete_scene(
+    presets_manager: Extension<PresetsManager>,
+    Path(scene_index): Path<usize>,
+) -> Result<(), StatusCode> {
+    Ok(presets_manager.delete_scene(scene_index)?)
+}
+
+async fn rename_scene(
+    presets_manager: Extension<PresetsManager>,
+    Path(scene_index): Path<usize>,
+    Json(payload): Json<RenameSceneRequest>,
+) -> Result<(), StatusCode> {
+    Ok(presets_manager.rename_scene(scene_index, payload.name)?)
+}
+
+async fn select_scene(
+    presets_manager: Extension<PresetsManager>,
+    Path(scene_index): Path<usize>,
+) -> Result<(), StatusCode> {
+    Ok(presets_manager.select_scene(scene_index)?)
+}
+
+impl From<SceneError> for StatusCode {
+    fn from(value: SceneError) -> Self {
+        match value {
+            SceneError::NotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct SceneBasicInfo {
+    pub index: usize,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetScenesResponse {
+    pub scenes: Vec<SceneBasicInfo>,
+    pub current_scene_index: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateSceneRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateSceneResponse {
+    pub index: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RenameSceneRequest {
+    pub name: String,
+}
@@ -1,33 +1,41 @@
+use crate::managers::chain::error::Error as ChainError;
+use crate::managers::chain::ChainManager;
 use crate::managers::dsp::error::TransformProcessorError;
-use crate::managers::dsp::models::{ProcessorParameters, ProcessorType};
+use crate::managers::dsp::models::ProcessorType;
 use crate::managers::dsp::DSPManager;
 use crate::managers::file_system::error::Error as FileSystemError;
 use crate::managers::file_system::FileSystemManager;
-use crate::processors::frontline::create_processor_from_type;
+use crate::managers::history::error::Error as HistoryError;
+use crate::managers::history::HistoryManager;
 use crate::processors::frontline::models::Parameter;
 use crate::routes::api::v1::processors::models::requests::{
     CreateProcessorRequest, EditParameterRequest, MoveProcessorRequest,
     SetProcessorBypassedRequest, SwapProcessorRequest,
 };
 use crate::routes::api::v1::processors::models::responses::{
-    CreateProcessorResponse, GetStringParameterValuesResponse, SwapProcessorResponse,
+    CreateProcessorResponse, GetHistoryStatusResponse, GetStringParameterValuesResponse,
+    SwapProcessorResponse,
 };
 use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::routing::{delete, get, post, put};
 use axum::{Extension, Json, Router};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::{Arc, Mutex};
 
 mod models;
 
 pub fn create_router() -> Router {
     Router::new()
         .route("/", post(create_processor))
+        .route("/history", get(get_history_status))
+        .route("/undo", post(undo))
+        .route("/redo", post(redo))
         .route("/:processor_index", put(swap_processor))
         .route("/:processor_index", delete(delete_processor))
         .route("/:processor_index/bypassed", put(set_processor_bypassed))
         .route("/:processor_index/move", put(move_processor))
+        .route("/:processor_index/noise-profile", post(learn_noise_profile))
         .route(
             "/:processor_index/parameters/:parameter",
             put(edit_parameter),
@@ -39,88 +47,87 @@ pub fn create_router() -> Router {
 }
 
 async fn create_processor(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Json(payload): Json<CreateProcessorRequest>,
-) -> Json<CreateProcessorResponse> {
-    let mut dsp_manager = dsp_manager.lock().unwrap();
-    let (numeric_parameters, string_parameters) =
-        add_new_processor(&mut dsp_manager, payload.index, payload.processor_type);
-
-    Json(CreateProcessorResponse {
-        parameters: ProcessorParameters {
-            numeric: numeric_parameters,
-            string: string_parameters,
-        },
-    })
+) -> Result<Json<CreateProcessorResponse>, StatusCode> {
+    let processor = chain_manager.create_processor(payload.index, &payload.processor_type)?;
+    Ok(Json(CreateProcessorResponse {
+        parameters: processor.parameters,
+    }))
 }
 
 async fn swap_processor(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Path(processor_index): Path<usize>,
     Json(payload): Json<SwapProcessorRequest>,
-) -> Json<SwapProcessorResponse> {
-    let mut dsp_manager = dsp_manager.lock().unwrap();
-    dsp_manager.extract_processor(processor_index);
-
-    let (numeric_parameters, string_parameters) =
-        add_new_processor(&mut dsp_manager, processor_index, payload.processor_type);
-
-    Json(SwapProcessorResponse {
-        parameters: ProcessorParameters {
-            numeric: numeric_parameters,
-            string: string_parameters,
-        },
-    })
+) -> Result<Json<SwapProcessorResponse>, StatusCode> {
+    let processor = chain_manager.swap_processor(processor_index, &payload.processor_type)?;
+    Ok(Json(SwapProcessorResponse {
+        parameters: processor.parameters,
+    }))
 }
 
 async fn delete_processor(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Path(processor_index): Path<usize>,
-) {
-    let mut dsp_manager = dsp_manager.lock().unwrap();
-    dsp_manager.extract_processor(processor_index);
+) -> Result<(), StatusCode> {
+    Ok(chain_manager.delete_processor(processor_index)?)
 }
 
 async fn move_processor(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Path(processor_index): Path<usize>,
     Json(payload): Json<MoveProcessorRequest>,
-) {
-    let mut dsp_manager = dsp_manager.lock().unwrap();
-    let processor = dsp_manager.extract_processor(processor_index);
-
-    let destination_index = if payload.destination_index <= processor_index {
-        payload.destination_index
-    } else {
-        payload.destination_index - 1
-    };
-    dsp_manager.add_processor(destination_index, processor);
+) -> Result<(), StatusCode> {
+    Ok(chain_manager.move_processor(processor_index, payload.destination_index)?)
 }
 
 async fn set_processor_bypassed(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Path(processor_index): Path<usize>,
     Json(payload): Json<SetProcessorBypassedRequest>,
 ) -> Result<(), StatusCode> {
-    let dsp_manager = dsp_manager.lock().unwrap();
-    match dsp_manager.transform_processor_settings(processor_index, |processor_settings| {
-        processor_settings.bypassed = payload.bypassed
-    }) {
-        Ok(_) => Ok(()),
-        Err(TransformProcessorError::NotFound) => Err(StatusCode::NOT_FOUND),
-    }
+    Ok(chain_manager.set_bypassed(processor_index, payload.bypassed)?)
 }
 
 async fn edit_parameter(
-    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    chain_manager: Extension<ChainManager>,
     Path((processor_index, parameter)): Path<(usize, Parameter)>,
     Json(payload): Json<EditParameterRequest>,
+) -> Result<(), StatusCode> {
+    chain_manager.set_parameter(processor_index, parameter, payload.value)?;
+    Ok(())
+}
+
+async fn get_history_status(
+    history_manager: Extension<HistoryManager>,
+) -> Json<GetHistoryStatusResponse> {
+    Json(history_manager.get_status())
+}
+
+async fn undo(
+    history_manager: Extension<HistoryManager>,
+) -> Result<Json<GetHistoryStatusResponse>, StatusCode> {
+    Ok(Json(history_manager.undo()?))
+}
+
+async fn redo(
+    history_manager: Extension<HistoryManager>,
+) -> Result<Json<GetHistoryStatusResponse>, StatusCode> {
+    Ok(Json(history_manager.redo()?))
+}
+
+async fn learn_noise_profile(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    Path(processor_index): Path<usize>,
 ) -> Result<(), StatusCode> {
     let dsp_manager = dsp_manager.lock().unwrap();
+    let mut is_supported = false;
     match dsp_manager.transform_processor(processor_index, |processor| {
-        processor.set_parameter(parameter, payload.value)
+        is_supported = processor.learn_noise_profile()
     }) {
-        Ok(_) => Ok(()),
+        Ok(_) if is_supported => Ok(()),
+        Ok(_) => Err(StatusCode::BAD_REQUEST),
         Err(TransformProcessorError::NotFound) => Err(StatusCode::NOT_FOUND),
     }
 }
@@ -153,21 +160,21 @@ async fn get_string_parameter_values(
     }
 }
 
-fn add_new_processor(
-    dsp_manager: &mut MutexGuard<DSPManager>,
-    index: usize,
-    processor_type: ProcessorType,
-) -> (HashMap<Parameter, f32>, HashMap<Parameter, String>) {
-    let processor = create_processor_from_type(
-        &processor_type,
-        &dsp_manager.sample_rate,
-        &dsp_manager.buffer_size,
-    );
-    let numeric_parameters = processor.get_numeric_parameters();
-    let string_parameters = processor.get_string_parameters();
-    dsp_manager.add_processor(index, processor);
+impl From<TransformProcessorError> for StatusCode {
+    fn from(value: TransformProcessorError) -> Self {
+        match value {
+            TransformProcessorError::NotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
 
-    (numeric_parameters, string_parameters)
+impl From<ChainError> for StatusCode {
+    fn from(value: ChainError) -> Self {
+        match value {
+            ChainError::ProcessorNotFound => StatusCode::NOT_FOUND,
+            ChainError::UnsupportedParameter => StatusCode::BAD_REQUEST,
+        }
+    }
 }
 
 impl From<FileSystemError> for StatusCode {
@@ -176,7 +183,18 @@ impl From<FileSystemError> for StatusCode {
             FileSystemError::Conversion => StatusCode::INTERNAL_SERVER_ERROR,
             FileSystemError::Generic(_) => StatusCode::INTERNAL_SERVER_ERROR,
             FileSystemError::HomeDirectoryNotFound => StatusCode::NOT_FOUND,
+            FileSystemError::InvalidPath => StatusCode::BAD_REQUEST,
             FileSystemError::NotFound => StatusCode::NOT_FOUND,
         }
     }
 }
+
+impl From<HistoryError> for StatusCode {
+    fn from(value: HistoryError) -> Self {
+        match value {
+            HistoryError::NothingToRedo => StatusCode::CONFLICT,
+            HistoryError::NothingToUndo => StatusCode::CONFLICT,
+            HistoryError::OutOfSync => StatusCode::CONFLICT,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::managers::dsp::models::ProcessorParameters;
+use crate::managers::history::models::HistoryStatus;
 use crate::processors::frontline::models::Parameter;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -17,3 +18,5 @@ pub struct SwapProcessorResponse {
 pub struct GetStringParameterValuesResponse {
     pub values: HashMap<Parameter, Vec<String>>,
 }
+
+pub type GetHistoryStatusResponse = HistoryStatus;
@@ -0,0 +1,175 @@
+use crate::managers::database::error::Error as DatabaseError;
+use crate::managers::database::models::Setlist;
+use crate::managers::database::DatabaseManager;
+use crate::managers::presets::error::SetlistError;
+use crate::managers::presets::{PresetsManager, SetlistMove};
+use crate::routes::api::v1::setlists::models::{
+    CreateSetlistResponse, GetActiveSetlistResponse, GetSetlistResponse, GetSetlistsResponse,
+    NavigateSetlistResponse, SaveSetlistRequest, SetPositionRequest, SetlistBasicInfo,
+};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+use uuid::Uuid;
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_setlists))
+        .route("/", post(create_setlist))
+        .route("/active", get(get_active_setlist))
+        .route("/active/next", post(go_to_next_preset))
+        .route("/active/previous", post(go_to_previous_preset))
+        .route("/active/position", put(set_position))
+        .route("/:setlist_id", get(get_setlist))
+        .route("/:setlist_id", put(update_setlist))
+        .route("/:setlist_id", delete(delete_setlist))
+        .route("/:setlist_id/activate", post(activate_setlist))
+}
+
+async fn get_setlists(database_manager: Extension<DatabaseManager>) -> Json<GetSetlistsResponse> {
+    Json(GetSetlistsResponse {
+        setlists: database_manager
+            .get_setlists()
+            .await
+            .into_iter()
+            .map(|setlist| SetlistBasicInfo {
+                id: setlist.id,
+                name: setlist.name,
+                is_active: setlist.is_active,
+            })
+            .collect(),
+    })
+}
+
+async fn create_setlist(
+    database_manager: Extension<DatabaseManager>,
+    Json(payload): Json<SaveSetlistRequest>,
+) -> Result<Json<CreateSetlistResponse>, StatusCode> {
+    validate_preset_ids(&database_manager, &payload.preset_ids).await?;
+    let setlist = Setlist {
+        id: Uuid::new_v4(),
+        name: payload.name,
+        preset_ids: payload.preset_ids,
+        is_active: false,
+        position: 0,
+    };
+    database_manager.save_setlist(&setlist).await?;
+    Ok(Json(CreateSetlistResponse { id: setlist.id }))
+}
+
+async fn get_active_setlist(
+    database_manager: Extension<DatabaseManager>,
+) -> Json<GetActiveSetlistResponse> {
+    Json(database_manager.get_active_setlist().await)
+}
+
+async fn go_to_next_preset(
+    presets_manager: Extension<PresetsManager>,
+) -> Result<Json<NavigateSetlistResponse>, StatusCode> {
+    Ok(Json(
+        presets_manager
+            .move_in_active_setlist(SetlistMove::Next)
+            .await?,
+    ))
+}
+
+async fn go_to_previous_preset(
+    presets_manager: Extension<PresetsManager>,
+) -> Result<Json<NavigateSetlistResponse>, StatusCode> {
+    Ok(Json(
+        presets_manager
+            .move_in_active_setlist(SetlistMove::Previous)
+            .await?,
+    ))
+}
+
+async fn set_position(
+    presets_manager: Extension<PresetsManager>,
+    Json(payload): Json<SetPositionRequest>,
+) -> Result<Json<NavigateSetlistResponse>, StatusCode> {
+    Ok(Json(
+        presets_manager
+            .move_in_active_setlist(SetlistMove::Position(payload.position))
+            .await?,
+    ))
+}
+
+async fn get_setlist(
+    database_manager: Extension<DatabaseManager>,
+    Path(setlist_id): Path<Uuid>,
+) -> Result<Json<GetSetlistResponse>, StatusCode> {
+    database_manager
+        .get_setlist(setlist_id)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn update_setlist(
+    database_manager: Extension<DatabaseManager>,
+    Path(setlist_id): Path<Uuid>,
+    Json(payload): Json<SaveSetlistRequest>,
+) -> Result<(), StatusCode> {
+    let mut setlist = database_manager
+        .get_setlist(setlist_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+    validate_preset_ids(&database_manager, &payload.preset_ids).await?;
+    setlist.name = payload.name;
+    setlist.position = setlist
+        .position
+        .min(payload.preset_ids.len().saturating_sub(1));
+    setlist.preset_ids = payload.preset_ids;
+    Ok(database_manager.save_setlist(&setlist).await?)
+}
+
+async fn delete_setlist(
+    database_manager: Extension<DatabaseManager>,
+    Path(setlist_id): Path<Uuid>,
+) {
+    database_manager.delete_setlist(setlist_id).await;
+}
+
+async fn activate_setlist(
+    presets_manager: Extension<PresetsManager>,
+    Path(setlist_id): Path<Uuid>,
+) -> Result<Json<NavigateSetlistResponse>, StatusCode> {
+    Ok(Json(presets_manager.activate_setlist(setlist_id).await?))
+}
+
+async fn validate_preset_ids(
+    database_manager: &DatabaseManager,
+    preset_ids: &[Uuid],
+) -> Result<(), StatusCode> {
+    for preset_id in preset_ids {
+        if database_manager.get_preset(*preset_id).await.is_none() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    Ok(())
+}
+
+impl From<DatabaseError> for StatusCode {
+    fn from(value: DatabaseError) -> Self {
+        match value {
+            DatabaseError::Generic => StatusCode::INTERNAL_SERVER_ERROR,
+            // Another client saved the preset after the version was checked
+            DatabaseError::VersionConflict => StatusCode::PRECONDITION_FAILED,
+        }
+    }
+}
+
+impl From<SetlistError> for StatusCode {
+    fn from(value: SetlistError) -> Self {
+        match value {
+            SetlistError::Database => StatusCode::INTERNAL_SERVER_ERROR,
+            SetlistError::NoActiveSetlist => StatusCode::NOT_FOUND,
+            SetlistError::NotFound => StatusCode::NOT_FOUND,
+            SetlistError::PositionOutOfRange => StatusCode::NOT_FOUND,
+            SetlistError::PresetNotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::managers::database::models::{Preset, Setlist};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize)]
+pub struct SetlistBasicInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub is_active: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetSetlistsResponse {
+    pub setlists: Vec<SetlistBasicInfo>,
+}
+
+pub type GetSetlistResponse = Setlist;
+
+pub type GetActiveSetlistResponse = Option<Setlist>;
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveSetlistRequest {
+    pub name: String,
+    pub preset_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateSetlistResponse {
+    pub id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SetPositionRequest {
+    pub position: usize,
+}
+
+pub type NavigateSetlistResponse = Preset;
@@ -0,0 +1,81 @@
+use crate::managers::dsp::error::TempoError;
+use crate::managers::dsp::models::TempoSource;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::routes::api::v1::tempo::models::{
+    GetTempoResponse, SetTempoRequest, SetTempoSourceRequest, TapTempoResponse,
+};
+use axum::http::StatusCode;
+use axum::routing::{get, post, put};
+use axum::{Extension, Json, Router};
+use std::sync::{Arc, Mutex};
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new()
+        .route("/", get(get_tempo))
+        .route("/", put(set_tempo))
+        .route("/source", put(set_tempo_source))
+        .route("/tap", post(tap_tempo))
+}
+
+async fn get_tempo(dsp_manager: Extension<Arc<Mutex<DSPManager>>>) -> Json<GetTempoResponse> {
+    let dsp_manager = dsp_manager.lock().unwrap();
+    Json(GetTempoResponse {
+        tempo: dsp_manager.get_tempo(),
+        source: dsp_manager.get_tempo_source(),
+    })
+}
+
+async fn set_tempo(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+    Json(payload): Json<SetTempoRequest>,
+) -> Result<(), StatusCode> {
+    let tempo = dsp_manager.lock().unwrap().set_tempo(payload.tempo)?;
+    events_manager.publish(StateEvent::TempoChanged {
+        tempo,
+        source: TempoSource::Internal,
+    });
+    Ok(())
+}
+
+async fn set_tempo_source(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+    Json(payload): Json<SetTempoSourceRequest>,
+) {
+    let tempo = {
+        let mut dsp_manager = dsp_manager.lock().unwrap();
+        dsp_manager.set_tempo_source(payload.source);
+        dsp_manager.get_tempo()
+    };
+    events_manager.publish(StateEvent::TempoChanged {
+        tempo,
+        source: payload.source,
+    });
+}
+
+async fn tap_tempo(
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    events_manager: Extension<EventsManager>,
+) -> Result<Json<TapTempoResponse>, StatusCode> {
+    let tempo = dsp_manager.lock().unwrap().tap_tempo()?;
+    if let Some(tempo) = tempo {
+        events_manager.publish(StateEvent::TempoChanged {
+            tempo,
+            source: TempoSource::Internal,
+        });
+    }
+    Ok(Json(TapTempoResponse { tempo }))
+}
+
+impl From<TempoError> for StatusCode {
+    fn from(value: TempoError) -> Self {
+        match value {
+            TempoError::SlavedToMidiClock => StatusCode::CONFLICT,
+        }
+    }
+}
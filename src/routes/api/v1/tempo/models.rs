@@ -0,0 +1,23 @@
+use crate::managers::dsp::models::TempoSource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GetTempoResponse {
+    pub tempo: f32,
+    pub source: TempoSource,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SetTempoRequest {
+    pub tempo: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SetTempoSourceRequest {
+    pub source: TempoSource,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TapTempoResponse {
+    pub tempo: Option<f32>,
+}
@@ -0,0 +1,170 @@
+use crate::managers::cache::CacheManager;
+use crate::managers::chain::error::Error as ChainError;
+use crate::managers::chain::ChainManager;
+use crate::managers::dsp::DSPManager;
+use crate::managers::events::models::StateEvent;
+use crate::managers::events::EventsManager;
+use crate::routes::api::v1::ws::models::{WebSocketCommand, WebSocketReply};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use futures::stream::SplitSink;
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::error::RecvError;
+
+mod models;
+
+pub fn create_router() -> Router {
+    Router::new().route("/", get(connect))
+}
+
+async fn connect(
+    web_socket: WebSocketUpgrade,
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    chain_manager: Extension<ChainManager>,
+    events_manager: Extension<EventsManager>,
+) -> Response {
+    web_socket.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            dsp_manager,
+            cache_manager,
+            chain_manager,
+            events_manager,
+        )
+    })
+}
+
+async fn handle_socket(
+    socket: WebSocket,
+    dsp_manager: Extension<Arc<Mutex<DSPManager>>>,
+    cache_manager: Extension<Arc<Mutex<CacheManager>>>,
+    chain_manager: Extension<ChainManager>,
+    events_manager: Extension<EventsManager>,
+) {
+    let mut events = events_manager.subscribe();
+    let (mut sender, mut receiver) = socket.split();
+
+    if send(&mut sender, &get_snapshot(&dsp_manager, &cache_manager))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            message = receiver.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(message) = handle_command(&text, &dsp_manager, &chain_manager, &events_manager) {
+                        if send(&mut sender, &WebSocketReply::Error { message }).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            event = events.recv() => {
+                let result = match event {
+                    Ok(event) => send(&mut sender, &event).await,
+                    // Too many events were missed: the client is better off starting over
+                    Err(RecvError::Lagged(_)) => {
+                        send(&mut sender, &get_snapshot(&dsp_manager, &cache_manager)).await
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn handle_command(
+    text: &str,
+    dsp_manager: &Arc<Mutex<DSPManager>>,
+    chain_manager: &ChainManager,
+    events_manager: &EventsManager,
+) -> Result<(), String> {
+    let command: WebSocketCommand =
+        serde_json::from_str(text).map_err(|error| format!("invalid command: {}", error))?;
+
+    match command {
+        WebSocketCommand::SetParameter {
+            index,
+            parameter,
+            value,
+        } => {
+            chain_manager.set_parameter(index, parameter, value)?;
+        }
+        WebSocketCommand::SetBypassed { index, bypassed } => {
+            chain_manager.set_bypassed(index, bypassed)?
+        }
+        WebSocketCommand::CreateProcessor {
+            index,
+            processor_type,
+        } => {
+            chain_manager.create_processor(index, &processor_type)?;
+        }
+        WebSocketCommand::SwapProcessor {
+            index,
+            processor_type,
+        } => {
+            chain_manager.swap_processor(index, &processor_type)?;
+        }
+        WebSocketCommand::MoveProcessor {
+            index,
+            destination_index,
+        } => chain_manager.move_processor(index, destination_index)?,
+        WebSocketCommand::DeleteProcessor { index } => chain_manager.delete_processor(index)?,
+        WebSocketCommand::SetExpression { position } => {
+            let position = {
+                let mut dsp_manager = dsp_manager.lock().unwrap();
+                dsp_manager.set_expression_position(position);
+                dsp_manager.get_expression_position()
+            };
+            events_manager.publish(StateEvent::ExpressionChanged { position });
+        }
+    }
+    Ok(())
+}
+
+fn get_snapshot(
+    dsp_manager: &Arc<Mutex<DSPManager>>,
+    cache_manager: &Arc<Mutex<CacheManager>>,
+) -> WebSocketReply {
+    WebSocketReply::Snapshot {
+        current_preset_id: cache_manager.lock().unwrap().current_preset_id,
+        processors: dsp_manager.lock().unwrap().get_processors_info(),
+    }
+}
+
+async fn send<Payload: Serialize>(
+    sender: &mut SplitSink<WebSocket, Message>,
+    payload: &Payload,
+) -> Result<(), axum::Error> {
+    match serde_json::to_string(payload) {
+        Ok(text) => sender.send(Message::Text(text)).await,
+        Err(error) => {
+            tracing::error!("cannot serialise websocket message: {}", error);
+            Ok(())
+        }
+    }
+}
+
+impl From<ChainError> for String {
+    fn from(value: ChainError) -> Self {
+        match value {
+            ChainError::ProcessorNotFound => "processor not found".to_string(),
+            ChainError::UnsupportedParameter => {
+                "parameter not supported by the processor".to_string()
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::managers::dsp::models::{ProcessorInfo, ProcessorType};
+use crate::processors::frontline::models::{Parameter, ParameterValue};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketCommand {
+    SetParameter {
+        index: usize,
+        parameter: Parameter,
+        value: ParameterValue,
+    },
+    SetBypassed {
+        index: usize,
+        bypassed: bool,
+    },
+    CreateProcessor {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    SwapProcessor {
+        index: usize,
+        processor_type: ProcessorType,
+    },
+    MoveProcessor {
+        index: usize,
+        destination_index: usize,
+    },
+    DeleteProcessor {
+        index: usize,
+    },
+    SetExpression {
+        position: f32,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketReply {
+    Snapshot {
+        current_preset_id: Option<Uuid>,
+        processors: Vec<ProcessorInfo>,
+    },
+    Error {
+        message: String,
+    },
+}
@@ -1,7 +1,15 @@
 use crate::settings::Settings;
 use axum::{serve, Router};
+use std::future::IntoFuture;
 use std::io;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::signal;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+
+static SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct Server {
     connection_string: String,
@@ -14,9 +22,52 @@ impl Server {
         }
     }
 
+    /// Serves until a shutdown signal is received. Requests in flight are given some time to
+    /// complete, while streaming connections are dropped.
     pub async fn start(&self, app: &Router) -> Result<(), io::Error> {
         let listener = TcpListener::bind(self.connection_string.clone()).await?;
         tracing::info!("server listening on {}", self.connection_string);
-        serve(listener, app.clone()).await
+        let shutdown = Arc::new(Notify::new());
+        let graceful_shutdown = shutdown.clone();
+        let server = serve(listener, app.clone())
+            .with_graceful_shutdown(async move { graceful_shutdown.notified().await })
+            .into_future();
+        let shutdown_timeout = async {
+            wait_for_shutdown_signal().await;
+            tracing::info!("shutdown requested, no longer accepting requests");
+            shutdown.notify_one();
+            sleep(SHUTDOWN_TIMEOUT).await;
+        };
+
+        tokio::select! {
+            result = server => result,
+            _ = shutdown_timeout => {
+                tracing::warn!("some connections are still open, closing them");
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Resolves on Ctrl+C or, on Unix, SIGTERM
+pub async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        let _ = signal::ctrl_c().await;
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
     }
 }
@@ -1,6 +1,6 @@
-use crate::managers::dsp::models::{Device, Driver};
+use crate::managers::database::models::Storage;
+use crate::managers::dsp::models::{AudioConfig, Device, Driver};
 use clap::Parser;
-use cpal::{BufferSize, SampleRate, StreamConfig};
 
 #[derive(Parser, Debug)]
 pub struct Settings {
@@ -12,6 +12,8 @@ pub struct Settings {
     pub driver: Driver,
     #[arg(long, default_value = "false")]
     pub hifiberry_enabled: bool,
+    #[arg(long, default_value = "50")]
+    pub history_size: usize,
     #[arg(long, default_value = "127.0.0.1")]
     host: String,
     #[arg(long, default_value = "1")]
@@ -24,14 +26,30 @@ pub struct Settings {
     pub log_level: tracing::Level,
     #[arg(long, default_value = "2000")]
     pub max_latency_in_samples: usize,
+    #[arg(long, default_value = "100", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_preset_revisions: u64,
+    #[arg(long, default_value = "false")]
+    pub midi_enabled: bool,
     #[arg(long, default_value = "1")]
     pub output_channels: u16,
     #[arg(long, default_value = "default", value_parser = clap::value_parser!(Device))]
     pub output_device: Device,
     #[arg(long, default_value = "96000")]
     pub output_sample_rate: u32,
+    #[arg(long, default_value = "false")]
+    pub osc_enabled: bool,
+    #[arg(long, default_value = "9000")]
+    osc_port: i32,
     #[arg(long, default_value = "3000")]
     port: i32,
+    #[arg(long, default_value = "false")]
+    pub remove_duplicate_presets: bool,
+    #[arg(long, default_value = "false")]
+    pub resume_session: bool,
+    #[arg(long, default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    pub session_save_interval_seconds: u64,
+    #[arg(long, default_value = "mongo")]
+    pub storage: Storage,
 }
 
 impl Settings {
@@ -39,19 +57,21 @@ impl Settings {
         format!("{}:{}", self.host, self.port)
     }
 
-    pub fn audio_input_stream_config(&self) -> StreamConfig {
-        StreamConfig {
-            buffer_size: BufferSize::Fixed(self.buffer_size as u32),
-            channels: self.input_channels,
-            sample_rate: SampleRate(self.input_sample_rate),
-        }
+    pub fn osc_connection_string(&self) -> String {
+        format!("{}:{}", self.host, self.osc_port)
     }
 
-    pub fn audio_output_stream_config(&self) -> StreamConfig {
-        StreamConfig {
-            buffer_size: BufferSize::Fixed(self.buffer_size as u32),
-            channels: self.output_channels,
-            sample_rate: SampleRate(self.output_sample_rate),
+    pub fn audio_config(&self) -> AudioConfig {
+        AudioConfig {
+            driver: self.driver.clone(),
+            input_device: self.input_device.clone(),
+            output_device: self.output_device.clone(),
+            input_channels: self.input_channels,
+            output_channels: self.output_channels,
+            input_sample_rate: self.input_sample_rate,
+            output_sample_rate: self.output_sample_rate,
+            buffer_size: self.buffer_size,
+            max_latency_in_samples: self.max_latency_in_samples,
         }
     }
 }